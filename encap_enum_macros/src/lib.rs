@@ -0,0 +1,211 @@
+// Copyright (c) 2020 Jonathan
+
+/*!
+The `#[encap_enum]` attribute, which turns an ordinary `enum` into an [`encap_enum!`](https://docs.rs/encap_enum).
+
+The `enum` is written with the normal Rust syntax, so rustfmt and rust-analyzer can format and navigate it,
+and the type of the `encap_enum!` moves into the attribute.
+The output is the same struct with a constant for each variant, so `encap_enum` has to be a dependency as well.
+It is found through `Cargo.toml`, so it can be renamed there like any other dependency.
+
+## Example
+```rust
+use encap_enum_macros::encap_enum;
+
+#[encap_enum(pub u32)]
+#[flags]
+pub enum Style {
+    /// Redraws the whole window when it is resized.
+    Redraw,
+    ByteAlign,
+    Both = Redraw | ByteAlign,
+}
+
+fn main() {
+    assert_eq!(Style::Both.raw, 3);
+    assert_eq!(Style::Redraw | Style::ByteAlign, Style::Both);
+}
+```
+
+## Arguments
+- `#[encap_enum]`: the type defaults to `isize` and `raw` is private, like an `encap_enum!` without a type.
+- `#[encap_enum(u32)]`: the type of the `enum`, with a private `raw`.
+- `#[encap_enum(pub u32)]`: the visibility of `raw` followed by the type.
+
+## Attributes
+Every attribute of `encap_enum!` works, including `#[flags]`, `#[enumeration]`, `#[set]`, `#[ops(..)]`, `#[derives(..)]`, `#[serde(..)]`, `#[known(..)]` and the checks such as `#[unique]`.
+They have to be placed below `#[encap_enum]`, since the compiler does not know them before the `enum` is expanded:
+```rust
+use encap_enum_macros::encap_enum;
+
+#[encap_enum(u8)]
+#[enumeration]
+#[derives(PartialEq, Eq)]
+enum Color {
+    Red = 1,
+    Green,
+    Blue,
+}
+
+fn main() {
+    assert_eq!(Color::Blue.raw, 3);
+    assert_eq!(Color::from_raw(2), Some(Color::Green));
+}
+```
+
+## Errors
+Anything that an `encap_enum!` cannot hold is an error pointing at the offending code, such as a variant with fields:
+```compile_fail
+use encap_enum_macros::encap_enum;
+
+#[encap_enum(u32)]
+enum Shape {
+    Circle(u32),
+}
+# fn main(){}
+```
+or a `#[repr]` with an integer type, which cannot be applied to a struct:
+```compile_fail
+use encap_enum_macros::encap_enum;
+
+#[encap_enum]
+#[repr(u8)]
+enum Shape {
+    Circle,
+}
+# fn main(){}
+```
+*/
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use proc_macro_crate::{crate_name, FoundCrate};
+use quote::{quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Attribute, Error, Fields, ItemEnum, Type, Visibility};
+
+// The integer types that `#[repr]` accepts on an `enum` but not on a struct.
+const INTEGER_REPRS: &[&str] = &["u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize"];
+
+// The arguments of `#[encap_enum(..)]`: nothing, or the visibility of `raw` followed by the type.
+struct Args {
+    inner_vis: Visibility,
+    ty: Option<Type>,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Ok(Args{inner_vis: Visibility::Inherited, ty: None});
+        }
+        let inner_vis = input.parse()?;
+        if input.is_empty() {
+            return Err(input.error("expected the type of the `enum` after its visibility"));
+        }
+        let ty = input.parse()?;
+        Ok(Args{inner_vis, ty: Some(ty)})
+    }
+}
+
+/**
+Declares an `encap_enum!` with ordinary `enum` syntax.
+
+See the [crate level docs](index.html) for the arguments and the attributes that can be used with it.
+*/
+#[proc_macro_attribute]
+pub fn encap_enum(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as Args);
+    let item = parse_macro_input!(input as ItemEnum);
+    expand(args, item).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn expand(args: Args, item: ItemEnum) -> syn::Result<TokenStream2> {
+    check(&item)?;
+
+    let krate = encap_enum_path()?;
+    let ItemEnum{attrs, vis, ident, variants, ..} = item;
+    let inner_vis = args.inner_vis;
+    let raw = args.ty.as_ref().map_or_else(|| quote!(isize), |ty| quote!(#ty));
+    let ty = args.ty.map(|ty| quote!(: #inner_vis #ty));
+    let variants = variants.into_iter().map(|variant| {
+        let attrs = variant.attrs;
+        let name = variant.ident;
+        // Converts each value like `encap_enum!` does, but under the span of the value, so that a mismatch points there instead of at the attribute.
+        let value = variant.discriminant.map(|(eq, value)| {
+            quote_spanned!(value.span()=> #eq <#raw>::from_le(#krate::__private::typed::<#raw, _>(#value).to_le()))
+        });
+        quote!(#(#attrs)* #name #value)
+    });
+
+    Ok(quote! {
+        #krate::encap_enum!{
+            #(#attrs)*
+            #vis enum #ident #ty {
+                #(#variants,)*
+            }
+        }
+    })
+}
+
+// The path of the `encap_enum` crate, under the name it has in the `Cargo.toml` of the crate being compiled.
+fn encap_enum_path() -> syn::Result<TokenStream2> {
+    match crate_name("encap_enum") {
+        Ok(FoundCrate::Name(name)) => {
+            let name = Ident::new(&name, Span::call_site());
+            Ok(quote!(::#name))
+        }
+        // Examples and tests of `encap_enum` itself use it as an external crate.
+        Ok(FoundCrate::Itself) => Ok(quote!(::encap_enum)),
+        Err(error) => Err(Error::new(Span::call_site(), format!("`#[encap_enum]` needs `encap_enum` as a dependency: {}", error))),
+    }
+}
+
+// Reports everything that `encap_enum!` cannot represent, combined into one error.
+fn check(item: &ItemEnum) -> syn::Result<()> {
+    let mut errors: Vec<Error> = Vec::new();
+
+    if !item.generics.params.is_empty() || item.generics.where_clause.is_some() {
+        errors.push(Error::new(item.generics.span(), "an `encap_enum` cannot have generic parameters"));
+    }
+    if item.variants.is_empty() {
+        errors.push(Error::new(item.ident.span(), "an `encap_enum` needs at least one variant"));
+    }
+    for attr in &item.attrs {
+        if let Some(error) = check_repr(attr) {
+            errors.push(error);
+        }
+    }
+    for variant in &item.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            errors.push(Error::new(variant.fields.span(), "variants of an `encap_enum` cannot have fields"));
+        }
+    }
+
+    match errors.into_iter().reduce(|mut all, error| { all.combine(error); all }) {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+fn check_repr(attr: &Attribute) -> Option<Error> {
+    if !attr.path().is_ident("repr") {
+        return None;
+    }
+    let mut error = None;
+    let _ = attr.parse_nested_meta(|meta| {
+        if let Some(repr) = meta.path.get_ident().map(|ident| ident.to_string()).filter(|repr| INTEGER_REPRS.contains(&repr.as_str())) {
+            error = Some(Error::new(
+                meta.path.span(),
+                format!("`#[repr({0})]` cannot be applied to an `encap_enum`, use `#[encap_enum({0})]` instead", repr),
+            ));
+        }
+        if meta.input.peek(syn::token::Paren) {
+            meta.input.parse::<proc_macro2::Group>()?;
+        }
+        Ok(())
+    });
+    error
+}
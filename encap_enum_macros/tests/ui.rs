@@ -0,0 +1,6 @@
+// Checks the errors of `#[encap_enum]` against the `.stderr` file next to each case.
+#[test]
+fn ui() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,10 @@
+use encap_enum_macros::encap_enum;
+
+#[encap_enum(u32)]
+enum Shape {
+    Circle(u32),
+    Square { side: u32 },
+    Point,
+}
+
+fn main() {}
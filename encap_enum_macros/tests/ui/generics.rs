@@ -0,0 +1,6 @@
+use encap_enum_macros::encap_enum;
+
+#[encap_enum(u8)]
+enum Empty<T> {}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use encap_enum_macros::encap_enum;
+
+#[encap_enum(pub)]
+enum Shape {
+    Circle,
+}
+
+fn main() {}
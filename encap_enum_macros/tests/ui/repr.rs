@@ -0,0 +1,9 @@
+use encap_enum_macros::encap_enum;
+
+#[encap_enum]
+#[repr(C, u8)]
+enum Shape {
+    Circle,
+}
+
+fn main() {}
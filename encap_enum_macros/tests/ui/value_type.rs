@@ -0,0 +1,9 @@
+use encap_enum_macros::encap_enum;
+
+#[encap_enum(u8)]
+enum Color {
+    Red = 1,
+    Green = "x",
+}
+
+fn main() {}
@@ -8,9 +8,8 @@ A type-safe encapsulated `enum` useful for interacting with C bitmasks, abbrevia
 
 Internally `encap_enum` uses a struct, therefore to access the value of a variant use `.raw`.
 
-Terms such as `enum`, and `variant` are used throughout the doc even though the internal representation can be different,
-and the reason is because rust's canonical `enum` is used internally when omitting explicit declarations of data. In other words it acts like
-an `enum`, yet it is composed of many different constructs.
+Terms such as `enum`, and `variant` are used throughout the doc even though the internal representation is a struct with a constant for each variant.
+In other words it acts like an `enum`, yet it is composed of many different constructs.
 
 ## Example
 The `encap_enum!` allows for bit flags and other common manipulations of data. When the `enum`'s type is omitted such as the example below, the type defaults to `isize`.
@@ -49,21 +48,134 @@ Constants outside the enum can be used to initialize variants:
 ```rust
 # #[macro_use]
 # extern crate encap_enum;
-# fn main(){ assert_eq!(TakenFlags::Delta.raw, 128); assert_eq!(TakenFlags::Negative, (-56).into())}
+# fn main(){ assert_eq!(TakenFlags::Delta.raw, 128); assert_eq!(TakenFlags::Negative, -56)}
 const VALUE: i32 = 56;
 const OTHER: i32 = 72;
 encap_enum!{
     enum TakenFlags: i32 {
-        Omega = (enum TakenFlags) VALUE, // Omega = 56
-        Sigma = (enum TakenFlags) OTHER, // Sigma = 72
-        Delta = (enum TakenFlags) VALUE + (enum TakenFlags) OTHER, // Delta = 128
-        Negative = (enum TakenFlags) -VALUE, // Negative = -56
+        Omega = VALUE, // Omega = 56
+        Sigma = OTHER, // Sigma = 72
+        Delta = VALUE + OTHER, // Delta = 128
+        Negative = -VALUE, // Negative = -56
     }
 }
 ```
 
-The `enum_encap!` macro also supports enumeration like a normal fieldless enum by tapping into rust's own `enum`.
-A name must be provided to ensure namespace collision does not occur due to the limitations of interacting with rust's `enum`
+A value can also be a variant of another `encap_enum!` with the same type, which initializes the variant with its `raw` value.
+Using an `encap_enum!` of a different type is a compile error:
+```rust
+# #[macro_use]
+# extern crate encap_enum;
+# fn main(){ assert_eq!(Window::Resize.raw, 0x10); assert_eq!(Window::Default.raw, 0x12); }
+mod consts {
+    pub const BORDER: u32 = 0x02;
+}
+encap_enum!{
+    pub enum Style: u32 {
+        Resize = 0x10,
+    }
+}
+encap_enum!{
+    enum Window: u32 {
+        Border = crate::consts::BORDER,
+        Resize = Style::Resize,
+        Default = Border | Resize, // Default = 0x12
+    }
+}
+```
+Only a whole value is converted, so another `encap_enum!` that is part of a larger expression needs `.raw`.
+Like reading `.raw`, this only works where the `raw` of the other `encap_enum!` is visible:
+```compile_fail
+# #[macro_use]
+# extern crate encap_enum;
+# fn main(){}
+mod secret {
+    encap_enum!{
+        pub enum Key: u32 {
+            Master = 42,
+        }
+    }
+}
+encap_enum!{
+    pub enum Leak: pub u32 {
+        Copy = secret::Key::Master, // error: `raw` of `Key` is private
+    }
+}
+```
+
+A value can be any constant expression, including casts and calls to a `const fn`.
+A bare name refers to the variant of the same enum if there is one, and to whatever is in scope otherwise:
+```rust
+# #[macro_use]
+# extern crate encap_enum;
+# fn main(){ assert_eq!(Shifted::C.raw, 24); assert_eq!(Shifted::D.raw, 37); assert_eq!(Shifted::E.raw, 257); }
+const fn page(n: u32) -> u32 { n << 8 }
+encap_enum!{
+    enum Shifted: u32 {
+        A = 2,
+        B = 4,
+        C = (A | B) << 2, // C = 24
+        D = (B + 1) | (C + 8) | u8::MIN as u32, // D = 37
+        E = page(A >> 1) + 1, // E = 257
+    }
+}
+```
+
+Like a C `enum`, a variant without a value is one more than the variant before it, and the first variant defaults to `0`:
+```rust
+# #[macro_use]
+# extern crate encap_enum;
+# fn main(){ assert_eq!(Header::C.raw, 7); assert_eq!(Header::E.raw, 21); }
+encap_enum!{
+    enum Header: u32 {
+        A = 5,
+        B, // B = 6
+        C, // C = 7
+        D = 20,
+        E, // E = 21
+    }
+}
+```
+
+With `#[flags]` a variant without a value is the next unused bit instead, so flags can be declared without writing out every power of two.
+Variants with a value are still allowed, and the variants after them continue from the bit above their highest bit.
+A variant that does not fit in the type of the enum is a compile error:
+```rust
+# #[macro_use]
+# extern crate encap_enum;
+# fn main(){ assert_eq!(Perms::Exec.raw, 4); assert_eq!(Perms::All.raw, 7); assert_eq!(Perms::Sticky.raw, 8); }
+encap_enum!{
+    #[flags]
+    enum Perms: u32 {
+        Read, // Read = 1
+        Write, // Write = 2
+        Exec, // Exec = 4
+        All = Read | Write | Exec, // All = 7
+        Sticky, // Sticky = 8
+    }
+}
+```
+
+The `enum_encap!` macro also supports enumeration like a normal fieldless enum, which can be declared anywhere an `enum` can.
+An `enum` where no variant has a value is an enumeration unless it has `#[flags]`, and `#[enumeration]` makes any `enum` one:
+```rust
+#[macro_use]
+extern crate encap_enum;
+
+encap_enum!{
+    #[enumeration]
+    pub enum OtherFlags: pub isize {
+        Alpha,
+        Beta,
+        Charlie,
+    }
+}
+fn main() {
+    println!("{}", OtherFlags::Alpha.raw);
+}
+```
+
+Enums declared under a `mod` are always enumerations, and their path is the namespace you set:
 ```rust
 #[macro_use]
 extern crate encap_enum;
@@ -81,7 +193,41 @@ fn main() {
     println!("{}", flag::OtherFlags::Alpha.raw);
 }
 ```
-The internal rust enum, which the struct constants draws values from, is under the namespace you set.
+
+Enums in the same `mod` can have different types and visibilities, and the type defaults to `isize`.
+A variant can have a value, and the variants after it continue from it:
+```rust
+# #[macro_use]
+# extern crate encap_enum;
+# fn main(){ assert_eq!(status::Code::Busy.raw, 3); assert_eq!(status::Code::Closed.raw, 11); }
+encap_enum!{
+    mod status {
+        pub enum Code: pub u8 {
+            Ready = 1,
+            Waiting, // Waiting = 2
+            Busy, // Busy = 3
+            Closed = 10 + 1, // Closed = 11
+        }
+        pub enum Level {
+            Low,
+            High,
+        }
+    }
+}
+```
+
+The `encap_enum_macros` crate has a `#[encap_enum]` attribute that declares the same thing with ordinary `enum` syntax,
+which rustfmt and rust-analyzer understand:
+```ignore
+use encap_enum_macros::encap_enum;
+
+#[encap_enum(pub u32)]
+#[flags]
+pub enum Style {
+    Redraw,
+    ByteAlign,
+}
+```
 
 ## Visibility
 The visibility for both the `enum` and variants can be changed:
@@ -102,65 +248,430 @@ encap_enum!{
 ```
 
 ## Attributes
-Attributes can be used pretty much anywhere in the macro except right under the declaration (See Corner Cases section at the bottom for more details).
+Attributes can be placed on the `enum` and before any variant, like on a normal `enum`. Doc comments, `#[deprecated]` and other attributes on a variant are applied to its constant:
+```rust
+# #[macro_use]
+# extern crate encap_enum;
+# fn main(){}
+encap_enum!{
+    /// Window styles.
+    #[repr(C)]
+    pub enum Style: pub u32 {
+        /// Redraws the whole window when it is resized.
+        Redraw = 0x01,
+        /// Aligns the window on a byte boundary.
+        ByteAlign = 0x02,
+        #[deprecated = "use `ByteAlign`"]
+        Aligned = 0x02,
+    }
+}
+```
+
+A variant removed by `#[cfg]` is also left out of `VARIANTS`, `NAMES`, `all()` and every other method, and a variant without a value continues from the last variant that is kept, like on a normal `enum`:
+```rust
+# #[macro_use]
+# extern crate encap_enum;
+# fn main(){ assert_eq!(Signal::NAMES.len(), if cfg!(unix) { 3 } else { 2 }); }
+encap_enum!{
+    enum Signal: u32 {
+        Interrupt = 2,
+        #[cfg(unix)]
+        Quit,
+        Kill = 9,
+    }
+}
+```
 
 ## Traits
 ### Derived Traits
 The following traits are derived:
-- Debug
 - Copy
 - Clone
 - PartialEq
 - Eq
 - PartialOrd
+- Ord
 - Hash
 
+More traits can be derived with `#[derive(..)]` as usual, while `#[derives(..)]` replaces every trait in the list except `Copy` and `Clone`.
+`Debug` cannot be derived, since it is always implemented to print the variant names, as described below:
+```rust
+# #[macro_use]
+# extern crate encap_enum;
+# fn main(){ assert_eq!(Mode::default(), Mode::Off); }
+encap_enum!{
+    #[derives(PartialEq, Eq, Default)]
+    enum Mode: u8 {
+        Off,
+        On,
+    }
+}
+```
+
+### Debug and Display
+`Display` prints the variant names instead of the raw value. Flags are decomposed into every variant they contain, with any leftover bits printed in hex,
+while enumerations only print a name when the value matches a variant exactly.
+`Debug` wraps the same text in the name of the `enum`, and the alternate form `{:#?}` still prints the struct and its `raw` field.
+```rust
+# #[macro_use]
+# extern crate encap_enum;
+encap_enum!{
+    enum Flags: u32 {
+        A = 0x01,
+        B = 0x02,
+        C = 0x04,
+    }
+}
+fn main() {
+    assert_eq!(format!("{:?}", Flags::A | Flags::B | 0x40), "Flags(A | B | 0x40)");
+    assert_eq!(format!("{:#?}", Flags::A), "Flags {\n    raw: 1,\n}");
+    assert_eq!(format!("{}", Flags::A | Flags::C), "A | C");
+}
+```
+
+### FromStr
+`FromStr` accepts the output of `Display`: variant names and numbers separated by `|`. Numbers can be decimal, `0x` hexadecimal or `0b` binary.
+A failed parse returns a [`ParseError`] pointing at the token that could not be read.
+```rust
+# #[macro_use]
+# extern crate encap_enum;
+# use encap_enum::ParseErrorKind;
+encap_enum!{
+    enum Flags: u32 {
+        A = 0x01,
+        B = 0x02,
+    }
+}
+fn main() {
+    assert_eq!("A | 0x10".parse::<Flags>(), Ok(Flags::A | 0x10));
+
+    let input = "A | Q";
+    let error = input.parse::<Flags>().unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::UnknownName);
+    assert_eq!(error.token(input), "Q");
+}
+```
+
 ### Operators
-The following operators are implemented:
-- BitOr
-- Add
-- BitAnd
-- BitXor
-- Div
-- Mul
-- Shl
-- Shr
-- Sub
-- Rem
-- Not
-- Neg
-- From
-- AddAssign
-- SubAssign
-- MulAssign
-- BitAndAssign
-- BitOrAssign
-- BitXorAssign
-- DivAssign
-- RemAssign
+The following operators are implemented, in groups:
+- `bitwise`: BitOr, BitAnd, BitXor and Not, with BitOr and BitAnd also taking the type of the `enum`
+- `arithmetic`: Add, Sub, Mul, Div, Rem and Neg
+- `shift`: Shl and Shr, by another value or by a `u32`
+- `set`: BitOr, BitAnd, BitXor, Sub as the difference `a & !b`, and Not flipping only the bits used by a variant, with BitOr and BitAnd also taking the type of the `enum`
+- `assign`: BitOrAssign, BitAndAssign and BitXorAssign for `bitwise` and `set`, SubAssign for `set`, AddAssign, SubAssign, MulAssign, DivAssign and RemAssign for `arithmetic`, and ShlAssign and ShrAssign by a `u32` for `shift`
+
+Every operator also works on references, such as `&a | &b`.
+`From` the type of the `enum`, and `PartialEq` and `PartialOrd` with it, are always implemented:
+```rust
+# #[macro_use]
+# extern crate encap_enum;
+encap_enum!{
+    enum Flags: u32 {
+        A = 0x01,
+        B = 0x02,
+    }
+}
+fn main() {
+    let flags = Flags::A | 0x10;
+    assert_eq!(flags, 0x11);
+    assert!(&flags & &Flags::B < 1);
+    assert_eq!(Flags::A << 4, 0x10);
+}
+```
+`Neg` wraps like `wrapping_neg`, so it also works on unsigned types.
+
+The `arithmetic` group also adds `checked_*`, `wrapping_*` and `saturating_*` versions of the operators, and `shift` adds `checked_shl`, `checked_shr`, `wrapping_shl` and `wrapping_shr`.
+They behave like the methods of the same name on the integer type, so overflow can be handled without a panic:
+```rust
+# #[macro_use]
+# extern crate encap_enum;
+encap_enum!{
+    enum Level: u8 {
+        Low = 1,
+        High = 200,
+    }
+}
+fn main() {
+    assert_eq!(Level::High.checked_add(Level::High), None);
+    assert_eq!(Level::High.saturating_add(Level::High).raw, 255);
+    assert_eq!(Level::High.wrapping_add(Level::High).raw, 144);
+    assert_eq!((-Level::Low).raw, 255);
+}
+```
+Every group except `set` is implemented by default, and `#[ops(..)]` selects which ones are, so a set of flags can refuse arithmetic.
+`set` implements the same operators as `bitwise` and `-` like `arithmetic`, so it cannot be selected together with either of them:
+```rust,compile_fail
+# #[macro_use]
+# extern crate encap_enum;
+encap_enum!{
+    #[ops(bitwise, assign)]
+    enum Perms: u32 {
+        Read = 1,
+        Write = 2,
+    }
+}
+fn main() {
+    let perms = Perms::Read * Perms::Write; // error: `Mul` is not implemented
+}
+```
+
+`#[set]` is short for `#[ops(set, assign)]`, for flags that are used as a set:
+```rust
+# #[macro_use]
+# extern crate encap_enum;
+encap_enum!{
+    #[set]
+    enum Perms: u8 {
+        Read = 1,
+        Write = 2,
+        Exec = 4,
+    }
+}
+fn main() {
+    let mut perms = Perms::Read | Perms::Write;
+    perms -= Perms::Exec;
+    assert_eq!(perms - Perms::Write, Perms::Read);
+    assert_eq!(!perms, Perms::Exec);
+}
+```
+
+### Constant Expressions
+Operators cannot be used in a `const` or `static` item, so the operators also have `const fn` versions:
+`or`, `and`, `xor` and `not` with `bitwise` or `set`, `sub` with `set`, and `shl` and `shr` with `shift`.
+[`flags!`] combines variants by name:
+```rust
+# #[macro_use]
+# extern crate encap_enum;
+encap_enum!{
+    enum Perms: u32 {
+        Read = 1,
+        Write = 2,
+        Exec = 4,
+    }
+}
+const READ_WRITE: Perms = Perms::Read.or(Perms::Write);
+static EVERYTHING: Perms = flags!(Perms: Read | Write | Exec);
+fn main() {
+    assert_eq!(READ_WRITE, flags!(Perms: Read | Write));
+    assert_eq!(EVERYTHING.and(READ_WRITE.not()), Perms::Exec);
+    assert!(EVERYTHING.contains(Perms::Read.shl(1)));
+}
+```
+
+## Serde
+With the `serde` feature enabled every `enum` implements `Serialize` and `Deserialize`. The representation is chosen per `enum` with a `#[serde(..)]` attribute:
+- `#[serde(numeric)]`: the raw value as an integer. This is the default.
+- `#[serde(name)]`: a single string in the format of `Display`, such as `"Alpha"` or `"A | B"`.
+- `#[serde(names)]`: a list of variant names such as `["A", "B"]`, with leftover bits written as a hex string.
+
+Values that are not made of variants are kept when deserializing. Adding `strict`, as in `#[serde(names, strict)]`, rejects them instead.
+```rust
+# #[macro_use]
+# extern crate encap_enum;
+# fn main(){}
+# #[cfg(feature = "serde")]
+encap_enum!{
+    #[serde(names, strict)]
+    pub enum Perms: u32 {
+        Read = 1,
+        Write = 2,
+    }
+}
+```
 
 ## Methods
-- `iter() -> Iter`: An iterator over all the variants.
+- `iter() -> Iter`: An iterator over the raw value of all the variants.
+- `variants()`: An iterator over all the variants.
+- `names()`: An iterator over the name of every variant.
+- `iter_named()`: An iterator over `(name, variant)` pairs.
+    - All three iterators are double ended and know their length.
 - `get_bit(bit:u8)->bool`: query the state of the specified bit. 
     - Only available if inner visibility is public to the module using it.
 - `new(data: [type])`: initialize with arbitrary data. 
     - Only available if inner visibility is public to the module using it.
+- `to_le() -> [type]`: the raw value in little-endian byte order.
+    - Only available if inner visibility is public to the module using it.
 
-## Corner Cases
-Attributes cannot be placed before the first variant and there are no plans to fix this.
-```rust,ignore
+### Constants
+- `COUNT: usize`: the number of variants.
+- `VARIANTS: [Self; COUNT]`: every variant in declaration order.
+- `NAMES: [&str; COUNT]`: the name of every variant in declaration order.
+
+The variants are constants of the same type, so `COUNT`, `VARIANTS` and `NAMES` are reserved and cannot be used as variant names.
+
+```rust
 # #[macro_use]
 # extern crate encap_enum;
-# fn main(){}
-encap_enum!{    
-    enum Flag: u32 {
-        /// This doc comment causes errors.
-        Gamma = -54,
+encap_enum!{
+    pub mod color {
+        pub enum Color: pub u8 {
+            Red,
+            Green,
+            Blue,
+        }
+    }
+}
+use color::Color;
+fn main() {
+    assert_eq!(Color::COUNT, 3);
+    assert_eq!(Color::NAMES, ["Red", "Green", "Blue"]);
+    assert_eq!(Color::variants().next_back(), Some(Color::Blue));
+    for (name, color) in Color::iter_named() {
+        println!("{} = {}", name, color.raw);
+    }
+}
+```
+
+### Lookups
+- `from_raw(raw) -> Option<Self>`: the variant equal to `raw`.
+- `name() -> Option<&str>`: the name of the variant equal to `self`.
+- `from_name(name) -> Option<Self>`: the variant called `name`. `from_name_ignore_case` ignores ASCII case.
+- `index() -> Option<usize>` and `from_index(index) -> Option<Self>`: the position of a variant in `VARIANTS`, which is the order it is declared in.
+- `cmp_declaration(other) -> Ordering`: compares by declaration order instead of by value, for use with `sort_by`.
+
+`from_raw`, `name`, `index` and `from_index` are `const fn`.
+The lookups by value and `from_name` compile to a `match` over the variants, so they do not walk `iter()`.
+`from_name_ignore_case` is the exception: a macro cannot change the case of the names, so it compares `name` with every variant in turn.
+```rust
+# #[macro_use]
+# extern crate encap_enum;
+encap_enum!{
+    pub mod color {
+        pub enum Color: u8 {
+            Red,
+            Green,
+        }
+    }
+}
+use color::Color;
+fn main() {
+    assert_eq!(Color::from_raw(1), Some(Color::Green));
+    assert_eq!(Color::from_raw(2), None);
+    assert_eq!(Color::Red.name(), Some("Red"));
+    assert_eq!(Color::from_name_ignore_case("GREEN"), Some(Color::Green));
+    assert_eq!(Color::Green.index(), Some(1));
+    assert_eq!(Color::from_index(0), Some(Color::Red));
+}
+```
+
+### Flag Sets
+- `empty()`: a value with no bits set.
+- `all()`: the bitwise or of every variant.
+- `from_bits(bits) -> Option<Self>`: `None` if `bits` has a bit that no variant uses.
+- `from_bits_truncate(bits)`: drops the bits that no variant uses.
+- `from_bits_retain(bits)`: keeps every bit, even the ones no variant uses.
+- `contains(other) -> bool`: every bit of `other` is set.
+- `intersects(other) -> bool`: at least one bit of `other` is set.
+- `is_empty() -> bool`: no bits are set.
+- `is_all() -> bool`: every bit used by a variant is set.
+- `insert(other)`, `remove(other)`, `toggle(other)`: set, clear or flip the bits of `other` in place.
+- `set(other, value: bool)`: insert `other` when `value` is `true`, otherwise remove it.
+- `union(other)`, `intersection(other)`, `difference(other)`, `symmetric_difference(other)`: `|`, `&`, `& !` and `^` as `const fn`.
+- `complement()`: the bits used by a variant that are not set.
+- `remainder()`: the bits that no variant uses.
+- `iter_set()`: an iterator over the variants whose bits are all set, which is also what `for flag in value` walks.
+- `iter_set_names()`: an iterator over the names of those variants.
+
+```rust
+# #[macro_use]
+# extern crate encap_enum;
+encap_enum!{
+    enum Perms: u32 {
+        Read = 1,
+        Write = 2,
+        Exec = 4,
     }
 }
+fn main() {
+    let mut perms = Perms::Read.union(Perms::Write);
+    assert!(perms.contains(Perms::Read));
+    perms.remove(Perms::Read);
+    assert!(!perms.intersects(Perms::Read));
+    assert_eq!(perms.complement(), Perms::Read | Perms::Exec);
+
+    assert_eq!(Perms::from_bits(0b011), Some(Perms::Read | Perms::Write));
+    assert_eq!(Perms::from_bits(0b1000), None);
+    assert_eq!(Perms::from_bits_truncate(0b1001), Perms::Read);
+
+    let perms = Perms::Read | Perms::Exec | 0x10;
+    for perm in perms {
+        assert!(perm == Perms::Read || perm == Perms::Exec);
+    }
+    assert!(perms.iter_set_names().eq(["Read", "Exec"].iter().copied()));
+    assert_eq!(perms.remainder().raw, 0x10);
+}
+```
+
+## Known Variants
+An `encap_enum!` can hold any value, so a `match` on it always needs a `_` arm.
+`#[known(Name)]` also declares a normal `enum` called `Name`, with a variant for every variant and `Unknown` for any other value,
+which can be matched exhaustively. `to_known()`, `from_known()` and `From` convert in both directions:
+```rust
+# #[macro_use]
+# extern crate encap_enum;
+encap_enum!{
+    #[enumeration]
+    #[known(KnownColor)]
+    pub enum Color: u8 {
+        Red = 1,
+        Green,
+        Blue,
+    }
+}
+fn describe(color: Color) -> &'static str {
+    match color.to_known() {
+        KnownColor::Red => "red",
+        KnownColor::Green => "green",
+        KnownColor::Blue => "blue",
+        KnownColor::Unknown(_) => "unknown",
+    }
+}
+fn main() {
+    assert_eq!(describe(Color::Green), "green");
+    assert_eq!(describe(Color::new(9)), "unknown");
+    assert_eq!(Color::from(KnownColor::Unknown(9)), Color::new(9));
+    assert_eq!(KnownColor::from(Color::Blue), KnownColor::Blue);
+}
 ```
+When several variants share a value, `to_known()` returns the first one declared.
+
+## Checks
+Nothing stops two variants from having the same value unless one of these attributes asks for it:
+- `#[unique]`: no two variants have the same value.
+- `#[disjoint]`: no two variants share a bit.
+- `#[single_bit]`: every variant is exactly one bit.
 
+A failed check is a compile error that names the offending variants. Variants removed by `#[cfg]` are not checked:
+```rust
+# #[macro_use]
+# extern crate encap_enum;
+# fn main(){}
+encap_enum!{
+    #[flags]
+    #[disjoint]
+    #[single_bit]
+    enum Perms: u32 {
+        Read,
+        Write,
+        Exec,
+    }
+}
+```
+```compile_fail
+# #[macro_use]
+# extern crate encap_enum;
+# fn main(){}
+encap_enum!{
+    #[unique]
+    enum Code: u8 {
+        Ok = 0,
+        Error = 1,
+        Failed = 1, // error: `#[unique]`: `Error` and `Failed` have the same value
+    }
+}
+```
 
+## Corner Cases
 The `#[repr(C)]` attribute will work and make the enum more ffi compatible, however `#[repr(u8)]`, `#[repr(u16)]`, etc. will not compile because the internal representation that it will apply to is a struct.
 The equivalent of `#[repr(u32)]`, which would apply on an enum would look like this on an `encap_enum!` declaration:
 
@@ -178,377 +689,1557 @@ encap_enum!{
 ```
 */
 
-// Provides an implementation to any struct tuple with a single integer field.
-#[macro_export]
-#[doc(hidden)]
-macro_rules! __encap_enum_impl {
+/// The reason a string could not be parsed into an `encap_enum!` type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ParseErrorKind {
+    /// A token between two `|` separators was empty.
+    Empty,
+    /// A token was not the name of a variant.
+    UnknownName,
+    /// A token looked like a number but did not fit the enum's type.
+    InvalidNumber,
+}
 
-    ($name:ident, $type:ty) => {
-        impl core::ops::BitOr for $name {
-            type Output = Self;
-            fn bitor(self, right: Self) -> Self { Self{raw: self.raw | right.raw} }
-        }
-        impl core::ops::Add for $name {
-            type Output = Self;
-            fn add(self, right: Self) -> Self { Self{raw: self.raw + right.raw} }
-        }
-        impl core::ops::BitAnd for $name {
-            type Output = Self;
-            fn bitand(self, right: Self) -> Self { Self{raw: self.raw & right.raw} }
+/// The error returned by `FromStr` on `encap_enum!` types.
+///
+/// The first [`ParseError::TEXT_LEN`] bytes of the failing token are kept for [`ParseError::text`] and `Display`,
+/// and the whole token can be recovered with [`ParseError::token`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    span: core::ops::Range<usize>,
+    text: [u8; ParseError::TEXT_LEN],
+    text_len: usize,
+}
+
+impl ParseError {
+    /// The number of bytes of the failing token that are kept in the error.
+    pub const TEXT_LEN: usize = 32;
+
+    fn new(kind: ParseErrorKind, span: core::ops::Range<usize>, token: &str) -> Self {
+        let mut text_len = token.len().min(Self::TEXT_LEN);
+        while !token.is_char_boundary(text_len) {
+            text_len -= 1;
         }
-        impl core::ops::BitXor for $name {
+        let mut text = [0; Self::TEXT_LEN];
+        text[..text_len].copy_from_slice(&token.as_bytes()[..text_len]);
+        ParseError { kind, span, text, text_len }
+    }
+
+    /// What was wrong with the token.
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// The byte range of the failing token in the parsed string.
+    pub fn span(&self) -> core::ops::Range<usize> {
+        self.span.clone()
+    }
+
+    /// The failing token, given the string that was parsed.
+    pub fn token<'a>(&self, input: &'a str) -> &'a str {
+        &input[self.span()]
+    }
+
+    /// The failing token, cut to its first [`ParseError::TEXT_LEN`] bytes.
+    pub fn text(&self) -> &str {
+        core::str::from_utf8(&self.text[..self.text_len]).unwrap_or_default()
+    }
+
+    /// Returns `true` if [`ParseError::text`] is only the start of the failing token.
+    pub fn is_truncated(&self) -> bool {
+        self.text_len < self.span.len()
+    }
+}
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let dots = if self.is_truncated() { "..." } else { "" };
+        match self.kind {
+            ParseErrorKind::Empty => write!(f, "empty token at byte {}", self.span.start),
+            ParseErrorKind::UnknownName => write!(f, "unknown variant name `{}{}` at bytes {:?}", self.text(), dots, self.span),
+            ParseErrorKind::InvalidNumber => write!(f, "invalid number `{}{}` at bytes {:?}", self.text(), dots, self.span),
+        }
+    }
+}
+
+/// An iterator over the variants contained in a value, in declaration order.
+///
+/// Returned by `iter_set` and `IntoIterator` on `encap_enum!` types. Variants equal to zero are skipped.
+#[derive(Clone, Debug)]
+pub struct IterSet<T: 'static> {
+    named: __private::Named<T>,
+    value: T,
+}
+
+impl<T: __private::Member> IterSet<T> {
+    #[doc(hidden)]
+    pub fn __new(named: __private::Named<T>, value: T) -> Self {
+        IterSet { named, value }
+    }
+
+    fn next_named(&mut self) -> Option<(&'static str, T)> {
+        let value = self.value;
+        self.named.find(|&(_, variant)| value.is_member(variant))
+    }
+
+    fn next_back_named(&mut self) -> Option<(&'static str, T)> {
+        let value = self.value;
+        self.named.rfind(|&(_, variant)| value.is_member(variant))
+    }
+}
+
+impl<T: __private::Member> Iterator for IterSet<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<T> {
+        self.next_named().map(|(_, variant)| variant)
+    }
+}
+
+impl<T: __private::Member> DoubleEndedIterator for IterSet<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.next_back_named().map(|(_, variant)| variant)
+    }
+}
+
+impl<T: __private::Member> core::iter::FusedIterator for IterSet<T> {}
+
+/// An iterator over the names of the variants contained in a value, in declaration order.
+///
+/// Returned by `iter_set_names` on `encap_enum!` types.
+#[derive(Clone, Debug)]
+pub struct IterSetNames<T: 'static>(IterSet<T>);
+
+impl<T: __private::Member> IterSetNames<T> {
+    #[doc(hidden)]
+    pub fn __new(named: __private::Named<T>, value: T) -> Self {
+        IterSetNames(IterSet::__new(named, value))
+    }
+}
+
+impl<T: __private::Member> Iterator for IterSetNames<T> {
+    type Item = &'static str;
+    fn next(&mut self) -> Option<&'static str> {
+        self.0.next_named().map(|(name, _)| name)
+    }
+}
+
+impl<T: __private::Member> DoubleEndedIterator for IterSetNames<T> {
+    fn next_back(&mut self) -> Option<&'static str> {
+        self.0.next_back_named().map(|(name, _)| name)
+    }
+}
+
+impl<T: __private::Member> core::iter::FusedIterator for IterSetNames<T> {}
+
+// Support code for the macros, not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    use super::{ParseError, ParseErrorKind};
+
+    #[cfg(feature = "serde")]
+    pub use serde;
+
+    // The iterator returned by `iter_named`.
+    pub type Named<T> = core::iter::Zip<
+        core::iter::Copied<core::slice::Iter<'static, &'static str>>,
+        core::iter::Copied<core::slice::Iter<'static, T>>,
+    >;
+
+    // Types that can tell which of their variants a value is made of.
+    pub trait Member: Copy + 'static {
+        // `true` if `variant` is not zero and all of its bits are set in `self`.
+        fn is_member(&self, variant: Self) -> bool;
+    }
+
+    // Integer types that can back an `encap_enum!`.
+    pub trait Integer: Copy {
+        // Parses a decimal, `0x` hexadecimal or `0b` binary number.
+        // Hexadecimal and binary accept every bit pattern, so `0xffff_ffff` is `-1` for an `i32`.
+        fn parse_token(token: &str) -> Option<Self>;
+    }
+
+    // Types whose value can initialize a variant of an `encap_enum!` backed by `R`: `R` itself and every `encap_enum!` of type `R`.
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` cannot initialize a variant of type `{R}`",
+        label = "expected `{R}` or an `encap_enum!` of type `{R}`"
+    )]
+    pub trait Raw<R: Copy>: Copy {}
+
+    // Returns `value` unchanged, which gives an integer literal the type `R` before `to_le` is called on it.
+    // Both the integer types and every `encap_enum!` have a `const fn to_le`, and the one of an `encap_enum!` is only visible where its `raw` is.
+    pub const fn typed<R: Copy, T: Raw<R>>(value: T) -> T {
+        value
+    }
+
+    // The number of variants that are not removed by `#[cfg]`.
+    pub const fn count<T: Copy>(declared: &[Option<T>]) -> usize {
+        let mut count = 0;
+        let mut i = 0;
+        while i < declared.len() {
+            if declared[i].is_some() {
+                count += 1;
+            }
+            i += 1;
+        }
+        count
+    }
+
+    // The variants that are not removed by `#[cfg]`, where `M` is their `count`.
+    pub const fn variants<T: Copy, const N: usize, const M: usize>(declared: [Option<T>; N], fill: T) -> [T; M] {
+        let mut variants = [fill; M];
+        let (mut i, mut j) = (0, 0);
+        while i < N {
+            if let Some(variant) = declared[i] {
+                variants[j] = variant;
+                j += 1;
+            }
+            i += 1;
+        }
+        variants
+    }
+
+    // The names of the variants that are not removed by `#[cfg]`, where `M` is their `count`.
+    pub const fn names<T: Copy, const N: usize, const M: usize>(declared: [Option<T>; N], names: [&'static str; N]) -> [&'static str; M] {
+        let mut present = [""; M];
+        let (mut i, mut j) = (0, 0);
+        while i < N {
+            if declared[i].is_some() {
+                present[j] = names[i];
+                j += 1;
+            }
+            i += 1;
+        }
+        present
+    }
+
+    macro_rules! integer {
+        ($($type:ty)+) => {$(
+            impl Raw<$type> for $type {}
+
+            impl Integer for $type {
+                fn parse_token(token: &str) -> Option<Self> {
+                    let (shift, digits) = match token.get(..2) {
+                        Some("0x") | Some("0X") => (4, &token[2..]),
+                        Some("0b") | Some("0B") => (1, &token[2..]),
+                        _ => return token.parse().ok(),
+                    };
+                    let bits = (core::mem::size_of::<$type>() * 8) as u32;
+                    let mut value: $type = 0;
+                    let mut used = 0;
+                    let mut any = false;
+                    for c in digits.chars() {
+                        if c == '_' { continue; }
+                        let digit = c.to_digit(1 << shift)?;
+                        any = true;
+                        if used == 0 {
+                            used = 32 - digit.leading_zeros();
+                        } else {
+                            used += shift;
+                        }
+                        if used > bits { return None; }
+                        value = value << shift | digit as $type;
+                    }
+                    if any { Some(value) } else { None }
+                }
+            }
+        )+};
+    }
+    integer!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+    // Splits `input` on `|`, looks up every trimmed token and combines the results with `or`.
+    pub fn parse<T>(
+        input: &str,
+        mut lookup: impl FnMut(&str) -> Option<T>,
+        mut or: impl FnMut(T, T) -> T,
+    ) -> Result<T, ParseError> {
+        let mut result = None;
+        let mut start = 0;
+        for part in input.split('|') {
+            let token = part.trim();
+            let begin = start + (part.len() - part.trim_start().len());
+            let span = begin..begin + token.len();
+            start += part.len() + 1;
+
+            let value = match lookup(token) {
+                Some(value) => value,
+                None => {
+                    let kind = match token.chars().next() {
+                        None => ParseErrorKind::Empty,
+                        Some(c) if c.is_ascii_digit() || c == '-' || c == '+' => ParseErrorKind::InvalidNumber,
+                        Some(_) => ParseErrorKind::UnknownName,
+                    };
+                    return Err(ParseError::new(kind, span, token));
+                }
+            };
+            result = Some(match result {
+                Some(acc) => or(acc, value),
+                None => value,
+            });
+        }
+        Ok(result.expect("split always yields a token"))
+    }
+
+    // The message of a failed check, built in a `const` since `panic!` can only format a single `&str` there.
+    pub struct Message {
+        buf: [u8; 256],
+        len: usize,
+    }
+
+    impl Message {
+        pub const EMPTY: Self = Message { buf: [0; 256], len: 0 };
+
+        // Appends `text`, or `...` once it no longer fits.
+        pub const fn push(mut self, text: &str) -> Self {
+            let (text, dots) = (text.as_bytes(), b"...");
+            let text = if self.len + text.len() + dots.len() <= self.buf.len() { text } else { dots };
+            let mut i = 0;
+            while i < text.len() && self.len < self.buf.len() {
+                self.buf[self.len] = text[i];
+                self.len += 1;
+                i += 1;
+            }
+            self
+        }
+
+        pub const fn as_str(&self) -> &str {
+            match core::str::from_utf8(self.buf.split_at(self.len).0) {
+                Ok(text) => text,
+                Err(_) => "",
+            }
+        }
+    }
+}
+
+// Provides an implementation to any struct tuple with a single integer field.
+// The operators are implemented in the groups selected by `#[ops(..)]`.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __encap_enum_impl {
+
+    ($name:ident, $type:ty, [$($group:ident)*] $groups:tt) => {
+        impl ::core::convert::From<$type> for $name {
+            fn from(right: $type) -> Self { Self{raw: right} }
+        }
+        impl ::core::cmp::PartialEq<$type> for $name {
+            fn eq(&self, right: &$type) -> bool { self.raw == *right }
+        }
+        impl ::core::cmp::PartialOrd<$type> for $name {
+            fn partial_cmp(&self, right: &$type) -> ::core::option::Option<::core::cmp::Ordering> { self.raw.partial_cmp(right) }
+        }
+        $($crate::__encap_enum_impl!{@$group $name, $type, $groups})*
+    };
+
+    (@bitwise $name:ident, $type:ty, $groups:tt) => {
+        impl ::core::ops::BitOr for $name {
+            type Output = Self;
+            fn bitor(self, right: Self) -> Self { Self{raw: self.raw | right.raw} }
+        }
+        impl ::core::ops::BitAnd for $name {
+            type Output = Self;
+            fn bitand(self, right: Self) -> Self { Self{raw: self.raw & right.raw} }
+        }
+        impl ::core::ops::BitXor for $name {
             type Output = Self;
             fn bitxor(self, right: Self) -> Self { Self{raw: self.raw ^ right.raw} }
         }
-        impl core::ops::Div for $name {
+        impl ::core::ops::Not for $name {
             type Output = Self;
-            fn div(self, right: Self) -> Self { Self{raw: self.raw / right.raw} }
+            fn not(self) -> Self { Self{raw: !self.raw} }
+        }
+        impl $name {
+            $crate::__encap_enum_impl!{@const}
+
+            /// `!` as a `const fn`.
+            #[must_use]
+            pub const fn not(self) -> Self { Self{raw: !self.raw} }
+        }
+        $crate::__encap_enum_impl!{@raw $name, $type}
+        $crate::__encap_enum_impl!{@refs $name, BitOr bitor BitAnd bitand BitXor bitxor}
+        $crate::__encap_enum_impl!{@refs $name, Not not}
+        $crate::__encap_enum_impl!{@assign bitwise $groups $name, $type}
+    };
+    (@arithmetic $name:ident, $type:ty, $groups:tt) => {
+        impl ::core::ops::Add for $name {
+            type Output = Self;
+            fn add(self, right: Self) -> Self { Self{raw: self.raw + right.raw} }
+        }
+        impl ::core::ops::Sub for $name {
+            type Output = Self;
+            fn sub(self, right: Self) -> Self { Self{raw: self.raw - right.raw} }
         }
-        impl core::ops::Mul for $name {
+        impl ::core::ops::Mul for $name {
             type Output = Self;
             fn mul(self, right: Self) -> Self { Self{raw: self.raw * right.raw} }
         }
-        impl core::ops::Shl for $name {
+        impl ::core::ops::Div for $name {
+            type Output = Self;
+            fn div(self, right: Self) -> Self { Self{raw: self.raw / right.raw} }
+        }
+        impl ::core::ops::Rem for $name {
+            type Output = Self;
+            fn rem(self, right: Self) -> Self { Self{raw: self.raw % right.raw} }
+        }
+        // Wraps, so that it does not panic for unsigned types.
+        impl ::core::ops::Neg for $name {
+            type Output = Self;
+            fn neg(self) -> Self { Self{raw: self.raw.wrapping_neg()} }
+        }
+        $crate::__encap_enum_impl!{@refs $name, Add add Sub sub Mul mul Div div Rem rem}
+        $crate::__encap_enum_impl!{@refs $name, Neg neg}
+        impl $name {
+            $crate::__encap_enum_impl!{@checked checked_add checked_sub checked_mul checked_div checked_rem}
+            $crate::__encap_enum_impl!{@wrapping wrapping_add wrapping_sub wrapping_mul saturating_add saturating_sub saturating_mul}
+
+            /// The same as the method of the same name on the integer type, applied to `raw`.
+            pub const fn checked_neg(self) -> ::core::option::Option<Self> {
+                match self.raw.checked_neg() {
+                    ::core::option::Option::Some(raw) => ::core::option::Option::Some(Self{raw}),
+                    ::core::option::Option::None => ::core::option::Option::None,
+                }
+            }
+
+            /// The same as the method of the same name on the integer type, applied to `raw`.
+            pub const fn wrapping_neg(self) -> Self { Self{raw: self.raw.wrapping_neg()} }
+        }
+        $crate::__encap_enum_impl!{@assign arithmetic $groups $name, $type}
+    };
+    // Set operations, where `-` is the difference and `!` only flips the bits used by a variant.
+    (@set $name:ident, $type:ty, $groups:tt) => {
+        impl ::core::ops::BitOr for $name {
+            type Output = Self;
+            fn bitor(self, right: Self) -> Self { self.union(right) }
+        }
+        impl ::core::ops::BitAnd for $name {
+            type Output = Self;
+            fn bitand(self, right: Self) -> Self { self.intersection(right) }
+        }
+        impl ::core::ops::BitXor for $name {
+            type Output = Self;
+            fn bitxor(self, right: Self) -> Self { self.symmetric_difference(right) }
+        }
+        impl ::core::ops::Sub for $name {
+            type Output = Self;
+            fn sub(self, right: Self) -> Self { self.difference(right) }
+        }
+        impl ::core::ops::Not for $name {
+            type Output = Self;
+            fn not(self) -> Self { self.complement() }
+        }
+        impl $name {
+            $crate::__encap_enum_impl!{@const}
+
+            /// `!` as a `const fn`, which only flips the bits used by a variant.
+            #[must_use]
+            pub const fn not(self) -> Self { self.complement() }
+
+            /// `-` as a `const fn`, which clears the bits of `other`.
+            #[must_use]
+            pub const fn sub(self, other: Self) -> Self { self.difference(other) }
+        }
+        $crate::__encap_enum_impl!{@raw $name, $type}
+        $crate::__encap_enum_impl!{@refs $name, BitOr bitor BitAnd bitand BitXor bitxor Sub sub}
+        $crate::__encap_enum_impl!{@refs $name, Not not}
+        $crate::__encap_enum_impl!{@assign set $groups $name, $type}
+    };
+    (@shift $name:ident, $type:ty, $groups:tt) => {
+        impl ::core::ops::Shl for $name {
             type Output = Self;
             fn shl(self, right: Self) -> Self { Self{raw: self.raw << right.raw} }
         }
-        impl core::ops::Shr for $name {
+        impl ::core::ops::Shr for $name {
             type Output = Self;
             fn shr(self, right: Self) -> Self { Self{raw: self.raw >> right.raw} }
         }
-        impl core::ops::Sub for $name {
+        impl ::core::ops::Shl<u32> for $name {
             type Output = Self;
-            fn sub(self, right: Self) -> Self { Self{raw: self.raw - right.raw} }
+            fn shl(self, right: u32) -> Self { Self{raw: self.raw << right} }
         }
-        impl core::ops::Rem for $name {
+        impl ::core::ops::Shr<u32> for $name {
             type Output = Self;
-            fn rem(self, right: Self) -> Self { Self{raw: self.raw % right.raw} }
+            fn shr(self, right: u32) -> Self { Self{raw: self.raw >> right} }
+        }
+        $crate::__encap_enum_impl!{@refs $name, Shl shl Shr shr}
+        $crate::__encap_enum_impl!{@assign shift $groups $name, $type}
+        impl $name {
+            /// `<<` by a `u32` as a `const fn`.
+            #[must_use]
+            pub const fn shl(self, right: u32) -> Self { Self{raw: self.raw << right} }
+
+            /// `>>` by a `u32` as a `const fn`.
+            #[must_use]
+            pub const fn shr(self, right: u32) -> Self { Self{raw: self.raw >> right} }
+
+            /// The same as the method of the same name on the integer type, applied to `raw`.
+            pub const fn checked_shl(self, right: u32) -> ::core::option::Option<Self> {
+                match self.raw.checked_shl(right) {
+                    ::core::option::Option::Some(raw) => ::core::option::Option::Some(Self{raw}),
+                    ::core::option::Option::None => ::core::option::Option::None,
+                }
+            }
+
+            /// The same as the method of the same name on the integer type, applied to `raw`.
+            pub const fn checked_shr(self, right: u32) -> ::core::option::Option<Self> {
+                match self.raw.checked_shr(right) {
+                    ::core::option::Option::Some(raw) => ::core::option::Option::Some(Self{raw}),
+                    ::core::option::Option::None => ::core::option::Option::None,
+                }
+            }
+
+            /// The same as the method of the same name on the integer type, applied to `raw`.
+            pub const fn wrapping_shl(self, right: u32) -> Self { Self{raw: self.raw.wrapping_shl(right)} }
+
+            /// The same as the method of the same name on the integer type, applied to `raw`.
+            pub const fn wrapping_shr(self, right: u32) -> Self { Self{raw: self.raw.wrapping_shr(right)} }
         }
-        impl core::ops::Not for $name {
+    };
+    // The binary operators shared by `bitwise` and `set` as `const fn`.
+    (@const) => {
+        /// `|` as a `const fn`.
+        #[must_use]
+        pub const fn or(self, other: Self) -> Self { self.union(other) }
+
+        /// `&` as a `const fn`.
+        #[must_use]
+        pub const fn and(self, other: Self) -> Self { self.intersection(other) }
+
+        /// `^` as a `const fn`.
+        #[must_use]
+        pub const fn xor(self, other: Self) -> Self { self.symmetric_difference(other) }
+    };
+    // `|` and `&` with the type of the enum.
+    (@raw $name:ident, $type:ty) => {
+        impl ::core::ops::BitOr<$type> for $name {
             type Output = Self;
-            fn not(self) -> Self { Self{raw: !self.raw} }
+            fn bitor(self, right: $type) -> Self { Self{raw: self.raw | right} }
         }
-        impl core::ops::Neg for $name {
+        impl ::core::ops::BitAnd<$type> for $name {
             type Output = Self;
-            fn neg(self) -> Self { Self{raw: 0 - self.raw} }
+            fn bitand(self, right: $type) -> Self { Self{raw: self.raw & right} }
         }
-        impl core::convert::From<$type> for $name {
-            fn from(right: $type) -> Self { Self{raw: right} }
+    };
+    // The operators of the values applied to references.
+    (@refs $name:ident, $($trait:ident $method:ident)+) => {$(
+        $crate::__encap_enum_impl!{@ref $name, $trait $method}
+    )+};
+    (@ref $name:ident, Not not) => {
+        impl<'a> ::core::ops::Not for &'a $name {
+            type Output = $name;
+            fn not(self) -> $name { ::core::ops::Not::not(*self) }
+        }
+    };
+    (@ref $name:ident, Neg neg) => {
+        impl<'a> ::core::ops::Neg for &'a $name {
+            type Output = $name;
+            fn neg(self) -> $name { ::core::ops::Neg::neg(*self) }
+        }
+    };
+    (@ref $name:ident, $trait:ident $method:ident) => {
+        impl<'a> ::core::ops::$trait<&'a $name> for $name {
+            type Output = $name;
+            fn $method(self, right: &'a $name) -> $name { ::core::ops::$trait::$method(self, *right) }
         }
-        impl core::ops::AddAssign for $name {            
+        impl<'a> ::core::ops::$trait<$name> for &'a $name {
+            type Output = $name;
+            fn $method(self, right: $name) -> $name { ::core::ops::$trait::$method(*self, right) }
+        }
+        impl<'a, 'b> ::core::ops::$trait<&'a $name> for &'b $name {
+            type Output = $name;
+            fn $method(self, right: &'a $name) -> $name { ::core::ops::$trait::$method(*self, *right) }
+        }
+    };
+    // Methods that take another value and return `None` instead of overflowing.
+    (@checked $($method:ident)+) => {$(
+        /// The same as the method of the same name on the integer type, applied to `raw`.
+        pub const fn $method(self, right: Self) -> ::core::option::Option<Self> {
+            match self.raw.$method(right.raw) {
+                ::core::option::Option::Some(raw) => ::core::option::Option::Some(Self{raw}),
+                ::core::option::Option::None => ::core::option::Option::None,
+            }
+        }
+    )+};
+    // Methods that take another value and handle overflow themselves.
+    (@wrapping $($method:ident)+) => {$(
+        /// The same as the method of the same name on the integer type, applied to `raw`.
+        pub const fn $method(self, right: Self) -> Self { Self{raw: self.raw.$method(right.raw)} }
+    )+};
+    // `assign` adds the assignment operators of the other groups.
+    (@assign $name:ident, $type:ty, $groups:tt) => {};
+    (@assign bitwise [assign $($rest:ident)*] $name:ident, $type:ty) => {
+        impl ::core::ops::BitAndAssign for $name {
+            fn bitand_assign(&mut self, right: Self) { self.raw &= right.raw }
+        }
+        impl ::core::ops::BitOrAssign for $name {
+            fn bitor_assign(&mut self, right: Self) { self.raw |= right.raw }
+        }
+        impl ::core::ops::BitXorAssign for $name {
+            fn bitxor_assign(&mut self, right: Self) { self.raw ^= right.raw }
+        }
+    };
+    (@assign arithmetic [assign $($rest:ident)*] $name:ident, $type:ty) => {
+        impl ::core::ops::AddAssign for $name {
             fn add_assign(&mut self, right: Self) { self.raw += right.raw }
         }
-        impl core::ops::SubAssign for $name {            
+        impl ::core::ops::SubAssign for $name {
             fn sub_assign(&mut self, right: Self) { self.raw -= right.raw }
         }
-        impl core::ops::MulAssign for $name {            
+        impl ::core::ops::MulAssign for $name {
             fn mul_assign(&mut self, right: Self) { self.raw *= right.raw }
         }
-        impl core::ops::BitAndAssign for $name {            
+        impl ::core::ops::DivAssign for $name {
+            fn div_assign(&mut self, right: Self) { self.raw /= right.raw }
+        }
+        impl ::core::ops::RemAssign for $name {
+            fn rem_assign(&mut self, right: Self) { self.raw %= right.raw }
+        }
+    };
+    (@assign set [assign $($rest:ident)*] $name:ident, $type:ty) => {
+        impl ::core::ops::BitAndAssign for $name {
             fn bitand_assign(&mut self, right: Self) { self.raw &= right.raw }
         }
-        impl core::ops::BitOrAssign for $name {            
+        impl ::core::ops::BitOrAssign for $name {
             fn bitor_assign(&mut self, right: Self) { self.raw |= right.raw }
         }
-        impl core::ops::BitXorAssign for $name {            
+        impl ::core::ops::BitXorAssign for $name {
             fn bitxor_assign(&mut self, right: Self) { self.raw ^= right.raw }
         }
-        impl core::ops::DivAssign for $name {            
-            fn div_assign(&mut self, right: Self) { self.raw /= right.raw }
+        impl ::core::ops::SubAssign for $name {
+            fn sub_assign(&mut self, right: Self) { self.remove(right) }
         }
-        impl core::ops::RemAssign for $name {            
-            fn rem_assign(&mut self, right: Self) { self.raw %= right.raw }
+    };
+    (@assign shift [assign $($rest:ident)*] $name:ident, $type:ty) => {
+        impl ::core::ops::ShlAssign<u32> for $name {
+            fn shl_assign(&mut self, right: u32) { self.raw <<= right }
         }
-    }
+        impl ::core::ops::ShrAssign<u32> for $name {
+            fn shr_assign(&mut self, right: u32) { self.raw >>= right }
+        }
+    };
+    (@assign $group:ident [$other:ident $($rest:ident)*] $name:ident, $type:ty) => {
+        $crate::__encap_enum_impl!{@assign $group [$($rest)*] $name, $type}
+    };
+    (@assign $group:ident [] $name:ident, $type:ty) => {};
+
+    (@$group:ident $name:ident, $type:ty, $groups:tt) => {
+        compile_error!(concat!("unknown operator group `", stringify!($group), "`, expected `bitwise`, `set`, `arithmetic`, `shift` or `assign`"));
+    };
 }
 
 
+// Evaluates to `Some($value)` when the attributes of a variant keep it, and to `None` or `$removed` when a `#[cfg]` removes it.
+// The inner constant only exists when the attributes keep the variant, otherwise the outer one is used.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __encap_enum_cfg {
 
-/**
-A macro for bit flags and enumerations.
+    ([$($attr:tt)*] $type:ty, $value:expr) => {
+        $crate::__encap_enum_cfg!{[$($attr)*] $type, $value, ::core::option::Option::None}
+    };
+    ([$($attr:tt)*] $type:ty, $value:expr, $removed:expr) => {{
+        const VALUE: ::core::option::Option<$type> = $removed;
+        {
+            $($attr)*
+            const VALUE: ::core::option::Option<$type> = ::core::option::Option::Some($value);
+            VALUE
+        }
+    }};
+}
 
-See [crate level docs](https://docs.rs/encap_enum) for full documentation.
+// Provides the flag-set methods to any struct with a single integer field named `raw`.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __encap_enum_methods {
+
+    ($name:ident, $type:ty, $({[$($attr:tt)*] $val_name:ident})+) => {
+        const _: () = {
+            // Every declared variant, or `None` where a `#[cfg]` removes it.
+            #[allow(dead_code)]
+            struct __Declared;
+            #[allow(deprecated)]
+            impl __Declared {
+                const VARIANTS: [::core::option::Option<$name>; [$(stringify!($val_name),)+].len()] = [$(
+                    $crate::__encap_enum_cfg!{[$($attr)*] $name, $name :: $val_name},
+                )+];
+            }
 
-## Example
-```rust
-#[macro_use]
-extern crate encap_enum;
+            impl $name {
+                /// The number of variants.
+                pub const COUNT: usize = $crate::__private::count(&__Declared::VARIANTS);
 
-encap_enum!{
-    enum Flags{
-        A = 0x00,
-        B = 0x01,
-        C = 0x02,
+                /// Every variant in declaration order.
+                pub const VARIANTS: [Self; Self::COUNT] = $crate::__private::variants(__Declared::VARIANTS, Self{raw: 0});
+
+                /// The name of every variant in declaration order.
+                pub const NAMES: [&'static str; Self::COUNT] = $crate::__private::names(__Declared::VARIANTS, [$(stringify!($val_name),)+]);
+            }
+        };
+
+        impl $name {
+            /// An iterator over the raw value of every variant.
+            pub fn iter() -> ::core::slice::Iter<'static, $type> {
+                const _ARRAY: &[$type] = &{
+                    let mut raw = [0; $name::COUNT];
+                    let mut i = 0;
+                    while i < $name::COUNT {
+                        raw[i] = $name::VARIANTS[i].raw;
+                        i += 1;
+                    }
+                    raw
+                };
+                _ARRAY.iter()
+            }
+
+            /// An iterator over every variant.
+            pub fn variants() -> ::core::iter::Copied<::core::slice::Iter<'static, Self>> {
+                Self::VARIANTS.iter().copied()
+            }
+
+            /// An iterator over the name of every variant.
+            pub fn names() -> ::core::iter::Copied<::core::slice::Iter<'static, &'static str>> {
+                Self::NAMES.iter().copied()
+            }
+
+            /// An iterator over the name and value of every variant.
+            pub fn iter_named() -> $crate::__private::Named<Self> {
+                Self::names().zip(Self::variants())
+            }
+
+            /// An iterator over the variants contained in `self`.
+            pub fn iter_set(&self) -> $crate::IterSet<Self> {
+                $crate::IterSet::__new(Self::iter_named(), *self)
+            }
+
+            /// An iterator over the names of the variants contained in `self`.
+            pub fn iter_set_names(&self) -> $crate::IterSetNames<Self> {
+                $crate::IterSetNames::__new(Self::iter_named(), *self)
+            }
+
+            /// The bits of `self` that no variant uses.
+            #[must_use]
+            pub const fn remainder(self) -> Self { Self{raw: self.raw & !Self::all().raw} }
+
+            /// A value with no bits set.
+            pub const fn empty() -> Self { Self{raw: 0} }
+
+            /// A value with every bit used by a variant set.
+            pub const fn all() -> Self {
+                let mut raw = 0;
+                let mut i = 0;
+                while i < Self::COUNT {
+                    raw |= Self::VARIANTS[i].raw;
+                    i += 1;
+                }
+                Self{raw}
+            }
+
+            /// Converts `bits` into a value, returning `None` if any bit is not used by a variant.
+            pub const fn from_bits(bits: $type) -> ::core::option::Option<Self> {
+                if bits & !Self::all().raw == 0 { ::core::option::Option::Some(Self{raw: bits}) } else { ::core::option::Option::None }
+            }
+
+            /// Converts `bits` into a value, dropping any bit not used by a variant.
+            pub const fn from_bits_truncate(bits: $type) -> Self { Self{raw: bits & Self::all().raw} }
+
+            /// Converts `bits` into a value, keeping every bit.
+            pub const fn from_bits_retain(bits: $type) -> Self { Self{raw: bits} }
+
+            /// Returns `true` if every bit of `other` is also set in `self`.
+            pub const fn contains(&self, other: Self) -> bool { self.raw & other.raw == other.raw }
+
+            /// Returns `true` if `self` and `other` have at least one bit in common.
+            pub const fn intersects(&self, other: Self) -> bool { self.raw & other.raw != 0 }
+
+            /// Returns `true` if no bits are set.
+            pub const fn is_empty(&self) -> bool { self.raw == 0 }
+
+            /// Returns `true` if every bit used by a variant is set.
+            pub const fn is_all(&self) -> bool { self.contains(Self::all()) }
+
+            /// Sets the bits of `other`.
+            pub fn insert(&mut self, other: Self) { self.raw |= other.raw }
+
+            /// Clears the bits of `other`.
+            pub fn remove(&mut self, other: Self) { self.raw &= !other.raw }
+
+            /// Flips the bits of `other`.
+            pub fn toggle(&mut self, other: Self) { self.raw ^= other.raw }
+
+            /// Sets the bits of `other` when `value` is `true`, otherwise clears them.
+            pub fn set(&mut self, other: Self, value: bool) {
+                if value { self.insert(other) } else { self.remove(other) }
+            }
+
+            /// The bits set in either `self` or `other`.
+            #[must_use]
+            pub const fn union(self, other: Self) -> Self { Self{raw: self.raw | other.raw} }
+
+            /// The bits set in both `self` and `other`.
+            #[must_use]
+            pub const fn intersection(self, other: Self) -> Self { Self{raw: self.raw & other.raw} }
+
+            /// The bits set in `self` but not in `other`.
+            #[must_use]
+            pub const fn difference(self, other: Self) -> Self { Self{raw: self.raw & !other.raw} }
+
+            /// The bits set in exactly one of `self` and `other`.
+            #[must_use]
+            pub const fn symmetric_difference(self, other: Self) -> Self { Self{raw: self.raw ^ other.raw} }
+
+            /// The bits used by a variant that are not set in `self`.
+            #[must_use]
+            pub const fn complement(self) -> Self { Self{raw: !self.raw & Self::all().raw} }
+        }
+        impl $crate::__private::Member for $name {
+            fn is_member(&self, variant: Self) -> bool { !variant.is_empty() && self.contains(variant) }
+        }
+        impl ::core::iter::IntoIterator for $name {
+            type Item = Self;
+            type IntoIter = $crate::IterSet<Self>;
+            fn into_iter(self) -> $crate::IterSet<Self> { self.iter_set() }
+        }
     }
 }
 
-fn main() {
-    println!("A = {}", Flags::A.raw);
-}
-```
-*/
+
+// Provides lookups between raw values, variants and names. Each one is a `match` over the variants.
+// Called like `__encap_enum_variants!`, with the names of the variants prefixed by `_` and the variants followed by `{}`.
 #[macro_export]
-macro_rules! encap_enum {
-    (
-        $(
-            $(#[$outer_comment:meta])*
-            $outer_vis:vis enum $name:ident : $inner_vis:vis $type:ty {
+#[doc(hidden)]
+macro_rules! __encap_enum_lookup {
+
+    ($name:ident, $type:ty, [$({[$($attr:tt)*] $val_name:ident})+], [$($prev:tt)+] [$($variant:tt)+]) => {
+        const _: () = {
+            // The raw value of every variant as a pattern, which is `None` and never matches when a `#[cfg]` removes the variant.
+            struct __Lookup;
+            #[allow(deprecated, non_upper_case_globals)]
+            impl __Lookup {
                 $(
-                    $val_name:ident =
-                        $($li:literal $(- $sub_li:literal)* $(- $li_sub_id:ident)* $(- (enum $type0:ty) $li_sub_sid:ident )*)?
-                        $($id:ident $(- $sub_id:ident)* $(- $id_sub_li:literal)* $(- (enum $type1:ty) $id_sub_sid:ident )*)?                      
-                        $((enum $type2:ty) $($sid0:ident)? $(- $sid1:ident)* $(- $($sid_sub_li:literal)* )* )*                
-                        $((-$id1:ident))?
-
-                        $(|  $bitor_li:literal)*
-                        $(+  $add_li:literal)*
-                        $(&  $bitand_li:literal)*
-                        $(^  $bitxor_li:literal)*
-                        $(/  $div_li:literal)*
-                        $(*  $mul_li:literal)*
-                        $(<< $shl_li:literal)*
-                        $(>> $shr_li:literal)*
-
-                        $(|  $bitor_id:ident)*
-                        $(+  $add_id:ident)*
-                        $(&  $bitand_id:ident)*
-                        $(^  $bitxor_id:ident )*
-                        $(/  $div_id:ident    )*
-                        $(*  $mul_id:ident    )*
-                        $(<< $shl_id:ident    )*
-                        $(>> $shr_id:ident    )*
-
-                        $(|  (enum $bitor_type_sid:ty)  $bitor_sid:ident  )*
-                        $(+  (enum $add_type_sid:ty)    $add_sid:ident    )*
-                        $(&  (enum $bitand_type_sid:ty) $bitand_sid:ident )*
-                        $(^  (enum $bitxor_type_sid:ty) $bitxor_sid:ident )*
-                        $(/  (enum $div_type_sid:ty)    $div_sid:ident    )*
-                        $(*  (enum $mul_type_sid:ty)    $mul_sid:ident    )*
-                        $(<< (enum $shl_type_sid:ty)    $shl_sid:ident    )*
-                        $(>> (enum $shr_type_sid:ty)    $shr_sid:ident    )*
-
-                    ,$(#[$comment:meta])*
+                    const $val_name: ::core::option::Option<$type> = $crate::__encap_enum_cfg!{[$($attr)*] $type, $name :: $val_name .raw};
                 )+
             }
-        )+
-    ) => {
-        $(
-            $(#[$outer_comment])*
-            #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Hash)]
-            $outer_vis struct $name{
-                $inner_vis raw: $type            
+
+            // The position of every variant in `VARIANTS`, which is the number of variants before it that are kept.
+            struct __Position;
+            #[allow(non_upper_case_globals, dead_code)]
+            impl __Position {
+                $($crate::__encap_enum_lookup!{@position $prev $variant})+
             }
-            __encap_enum_impl!{$name, $type}
+
             impl $name {
-                $inner_vis const fn new(data: $type) -> Self{
-                    Self{raw: data}
+                /// Returns the position in `VARIANTS` of the variant equal to `self`, or `None` if no variant has that value.
+                ///
+                /// When several variants share a value the first one declared is used.
+                #[allow(unreachable_patterns)]
+                pub const fn index(&self) -> ::core::option::Option<usize> {
+                    match ::core::option::Option::Some(self.raw) {
+                        $(__Lookup::$val_name => ::core::option::Option::Some(__Position::$val_name),)+
+                        _ => ::core::option::Option::None,
+                    }
+                }
+
+                /// Returns the variant equal to `raw`, or `None` if no variant has that value.
+                #[allow(unreachable_patterns)]
+                pub const fn from_raw(raw: $type) -> ::core::option::Option<Self> {
+                    match ::core::option::Option::Some(raw) {
+                        $(__Lookup::$val_name => ::core::option::Option::Some(Self{raw}),)+
+                        _ => ::core::option::Option::None,
+                    }
                 }
 
-                pub fn iter() -> core::slice::Iter<'static, $type> {
+                /// Returns the name of the variant equal to `self`, or `None` if no variant has that value.
+                ///
+                /// When several variants share a value the first one declared is used.
+                #[allow(unreachable_patterns)]
+                pub const fn name(&self) -> ::core::option::Option<&'static str> {
+                    match ::core::option::Option::Some(self.raw) {
+                        $(__Lookup::$val_name => ::core::option::Option::Some(stringify!($val_name)),)+
+                        _ => ::core::option::Option::None,
+                    }
+                }
 
-                    const _ARRAY: &[$type] = &[$($name :: $val_name .raw,)+];
-                    _ARRAY.into_iter()
+                /// Returns the variant called `name`.
+                pub fn from_name(name: &str) -> ::core::option::Option<Self> {
+                    let raw = match name {
+                        $(stringify!($val_name) => __Lookup::$val_name,)+
+                        _ => ::core::option::Option::None,
+                    };
+                    raw.map(|raw| Self{raw})
                 }
+            }
+        };
 
-                $inner_vis fn get_bit(&self, bit:u8) -> bool{
+        impl $name {
+            /// Returns the variant at position `index` in `VARIANTS`.
+            pub const fn from_index(index: usize) -> ::core::option::Option<Self> {
+                if index < Self::COUNT { ::core::option::Option::Some(Self::VARIANTS[index]) } else { ::core::option::Option::None }
+            }
 
-                    self.raw & (1 << bit) != 0
+            /// Compares `self` and `other` by the order their variants are declared in.
+            ///
+            /// Values that are not a variant come after every variant and are compared by their raw value.
+            pub fn cmp_declaration(&self, other: &Self) -> ::core::cmp::Ordering {
+                match (self.index(), other.index()) {
+                    (::core::option::Option::Some(left), ::core::option::Option::Some(right)) => left.cmp(&right),
+                    (::core::option::Option::Some(_), ::core::option::Option::None) => ::core::cmp::Ordering::Less,
+                    (::core::option::Option::None, ::core::option::Option::Some(_)) => ::core::cmp::Ordering::Greater,
+                    (::core::option::Option::None, ::core::option::Option::None) => self.raw.cmp(&other.raw),
                 }
+            }
 
-                $(
-                    $(#[$comment])*
-                    #[allow(non_upper_case_globals)]
-                    pub const $val_name: $name = $name { raw :                    
-                        $($li $(- $sub_li)* $(- $name :: $li_sub_id .raw)* $(- $type0 :: new($li_sub_sid) .raw )*)?
-                        $($name :: $id .raw $(- $name :: $sub_id .raw)* $(- $id_sub_li)* $(- $type1 ::new($id_sub_sid) .raw )*)?
-                        $(<$type2> :: new($($sid0)? $(- $sid1)*) .raw $(- $($sid_sub_li)* )*)*                    
-                        $(- $name :: $id1 .raw)?
-
-                        $(|  $bitor_li  )*
-                        $(+  $add_li    )*
-                        $(&  $bitand_li )*
-                        $(^  $bitxor_li )*
-                        $(/  $div_li    )*
-                        $(*  $mul_li    )*
-                        $(<< $shl_li    )*
-                        $(>> $shr_li    )*
-
-                        $(|  $name :: $bitor_id .raw  )*
-                        $(+  $name :: $add_id .raw    )*
-                        $(&  $name :: $bitand_id .raw )*
-                        $(^  $name :: $bitxor_id .raw )*
-                        $(/  $name :: $div_id .raw    )*
-                        $(*  $name :: $mul_id .raw    )*
-                        $(<< $name :: $shl_id .raw    )*
-                        $(>> $name :: $shr_id .raw    )*
-
-                        $(|  <$bitor_type_sid>  :: new($bitor_sid)  .raw )*
-                        $(+  <$add_type_sid>    :: new($add_sid)    .raw )*
-                        $(&  <$bitand_type_sid> :: new($bitand_sid) .raw )*
-                        $(^  <$bitxor_type_sid> :: new($bitxor_sid) .raw )*
-                        $(/  <$div_type_sid>    :: new($div_sid)    .raw )*
-                        $(*  <$mul_type_sid>    :: new($mul_sid)    .raw )*
-                        $(<< <$shl_type_sid>    :: new($shl_sid)    .raw )*
-                        $(>> <$shr_type_sid>    :: new($shr_sid)    .raw )*
-                    };
-                )+
+            /// Returns the variant called `name`, ignoring ASCII case.
+            ///
+            /// Unlike `from_name` this compares `name` with every variant in turn.
+            pub fn from_name_ignore_case(name: &str) -> ::core::option::Option<Self> {
+                Self::iter_named().find(|(variant, _)| variant.eq_ignore_ascii_case(name)).map(|(_, value)| value)
             }
-        )+
+        }
     };
-    (
-        $(
-        $(#[$outer_comment:meta])*
-        $outer_vis:vis enum $name:ident {
+
+    (@position $prev:tt {}) => {};
+    (@position _ {[$($attr:tt)*] $val_name:ident}) => {
+        const $val_name: usize = 0;
+    };
+    (@position $prev:ident {[$($attr:tt)*] $val_name:ident}) => {
+        const $val_name: usize = __Position :: $prev + __Lookup :: $prev .is_some() as usize;
+    };
+}
+
+// Provides the checks selected with `#[unique]`, `#[disjoint]` and `#[single_bit]`, which fail to compile with the names of the offending variants.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __encap_enum_check {
+
+    (unique $name:ident) => {
+        $crate::__encap_enum_check!{@pairs $name, "unique", "have the same value", |a, b| a == b}
+    };
+    (disjoint $name:ident) => {
+        $crate::__encap_enum_check!{@pairs $name, "disjoint", "share bits", |a, b| a & b != 0}
+    };
+    (single_bit $name:ident) => {
+        const _: () = {
+            let mut i = 0;
+            while i < $name::COUNT {
+                if $name::VARIANTS[i].raw.count_ones() != 1 {
+                    let message = $crate::__private::Message::EMPTY
+                        .push("`#[single_bit]`: `")
+                        .push($name::NAMES[i])
+                        .push("` is not a single bit");
+                    panic!("{}", message.as_str());
+                }
+                i += 1;
+            }
+        };
+    };
+    (@pairs $name:ident, $check:literal, $problem:literal, |$a:ident, $b:ident| $conflict:expr) => {
+        const _: () = {
+            let mut i = 0;
+            while i < $name::COUNT {
+                let mut j = i + 1;
+                while j < $name::COUNT {
+                    let ($a, $b) = ($name::VARIANTS[i].raw, $name::VARIANTS[j].raw);
+                    if $conflict {
+                        let message = $crate::__private::Message::EMPTY
+                            .push(concat!("`#[", $check, "]`: `"))
+                            .push($name::NAMES[i])
+                            .push("` and `")
+                            .push($name::NAMES[j])
+                            .push(concat!("` ", $problem));
+                        panic!("{}", message.as_str());
+                    }
+                    j += 1;
+                }
+                i += 1;
+            }
+        };
+    };
+}
+
+// Provides the `enum` named by `#[known(..)]`, which has a variant for every variant and `Unknown` for any other value.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __encap_enum_known {
+
+    ([] $($item:tt)*) => {};
+    ([$known:ident] [$($outer_vis:tt)*] $name:ident, $type:ty, $({[$($attr:tt)*] $val_name:ident})+) => {
+        #[doc = concat!("Every variant of [`", stringify!($name), "`], and `Unknown` for any other value.")]
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+        $($outer_vis)* enum $known {
             $(
-                $val_name:ident =
-                    $($li:literal $(- $sub_li:literal)* $(- $li_sub_id:ident)* $(- (enum $type0:ty) $li_sub_sid:ident )*)?
-                    $($id:ident $(- $sub_id:ident)* $(- $id_sub_li:literal)* $(- (enum $type1:ty) $id_sub_sid:ident )*)?                      
-                    $((enum $type2:ty) $($sid0:ident)? $(- $sid1:ident)* $(- $($sid_sub_li:literal)* )* )*                
-                    $((-$id1:ident))?
-                
-                    $(|  $bitor_li:literal)*
-                    $(+  $add_li:literal)*
-                    $(&  $bitand_li:literal)*
-                    $(^  $bitxor_li:literal)*
-                    $(/  $div_li:literal)*
-                    $(*  $mul_li:literal)*
-                    $(<< $shl_li:literal)*
-                    $(>> $shr_li:literal)*
-                
-                    $(|  $bitor_id:ident)*
-                    $(+  $add_id:ident)*
-                    $(&  $bitand_id:ident)*
-                    $(^  $bitxor_id:ident )*
-                    $(/  $div_id:ident    )*
-                    $(*  $mul_id:ident    )*
-                    $(<< $shl_id:ident    )*
-                    $(>> $shr_id:ident    )*
-
-                    $(|  (enum $bitor_type_sid:ty)  $bitor_sid:ident  )*
-                    $(+  (enum $add_type_sid:ty)    $add_sid:ident    )*
-                    $(&  (enum $bitand_type_sid:ty) $bitand_sid:ident )*
-                    $(^  (enum $bitxor_type_sid:ty) $bitxor_sid:ident )*
-                    $(/  (enum $div_type_sid:ty)    $div_sid:ident    )*
-                    $(*  (enum $mul_type_sid:ty)    $mul_sid:ident    )*
-                    $(<< (enum $shl_type_sid:ty)    $shl_sid:ident    )*
-                    $(>> (enum $shr_type_sid:ty)    $shr_sid:ident    )*
-                ,$(#[$comment:meta])*
+                $($attr)*
+                $val_name,
             )+
+            /// A value that is not a variant.
+            Unknown($type),
         }
-    )+
-    ) => {
-        $(
-            $(#[$outer_comment])?
-            #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Hash)]
-            $outer_vis struct $name{
-                raw: isize            
+
+        #[allow(deprecated)]
+        const _: () = {
+            // The known variant at every position of `VARIANTS`, and every known variant as a pattern, which is `None` and never matches when a `#[cfg]` removes the variant.
+            struct __Known;
+            #[allow(non_upper_case_globals)]
+            impl __Known {
+                const VARIANTS: [$known; $name::COUNT] = $crate::__private::variants([$(
+                    $crate::__encap_enum_cfg!{[$($attr)*] $known, $known::$val_name},
+                )+], $known::Unknown(0));
+                $(
+                    const $val_name: ::core::option::Option<$known> = $crate::__encap_enum_cfg!{[$($attr)*] $known, $known::$val_name};
+                )+
             }
-            __encap_enum_impl!{$name, isize}
+
             impl $name {
-                const fn new(data: isize) -> Self{
-                    Self{raw: data}
+                #[doc = concat!("Converts to a [`", stringify!($known), "`], which can be matched exhaustively.")]
+                ///
+                /// When several variants share a value the first one declared is used.
+                pub const fn to_known(self) -> $known {
+                    match self.index() {
+                        ::core::option::Option::Some(index) => __Known::VARIANTS[index],
+                        ::core::option::Option::None => $known::Unknown(self.raw),
+                    }
                 }
-                
-                pub fn iter() -> core::slice::Iter<'static, isize> {
-                    const _ARRAY: &[isize] = &[$($name :: $val_name .raw,)+];
-                    _ARRAY.into_iter()
+
+                #[doc = concat!("Converts from a [`", stringify!($known), "`].")]
+                #[allow(unreachable_patterns)]
+                pub const fn from_known(known: $known) -> Self {
+                    // A pattern only matches when its variant is kept, so the `None` arms are never taken.
+                    match ::core::option::Option::Some(known) {
+                        ::core::option::Option::Some($known::Unknown(raw)) => Self{raw},
+                        $(__Known::$val_name => match $crate::__encap_enum_cfg!{[$($attr)*] $name, $name :: $val_name} {
+                            ::core::option::Option::Some(value) => value,
+                            ::core::option::Option::None => Self{raw: 0},
+                        },)+
+                        _ => Self{raw: 0},
+                    }
                 }
+            }
+        };
 
-                $(
-                    $(#[$comment])*
-                    #[allow(non_upper_case_globals)]
-                    pub const $val_name: $name = $name { raw :
-                        $($li $(- $sub_li)* $(- $name :: $li_sub_id .raw)* $(- $type0 ($li_sub_sid) .raw )*)?
-                        $($name :: $id .raw $(- $name :: $sub_id .raw)* $(- $id_sub_li)* $(- $type1 ($id_sub_sid) .raw )*)?
-                        $($type2 ($($sid0)? $(- $sid1)*) .raw $(- $($sid_sub_li)* )*)*                    
-                        $(- $name :: $id1 .raw)?
-
-                        $(|  $bitor_li  )*
-                        $(+  $add_li    )*
-                        $(&  $bitand_li )*
-                        $(^  $bitxor_li )*
-                        $(/  $div_li    )*
-                        $(*  $mul_li    )*
-                        $(<< $shl_li    )*
-                        $(>> $shr_li    )*
-
-                        $(|  $name :: $bitor_id .raw  )*
-                        $(+  $name :: $add_id .raw    )*
-                        $(&  $name :: $bitand_id .raw )*
-                        $(^  $name :: $bitxor_id .raw )*
-                        $(/  $name :: $div_id .raw    )*
-                        $(*  $name :: $mul_id .raw    )*
-                        $(<< $name :: $shl_id .raw    )*
-                        $(>> $name :: $shr_id .raw    )*
-
-                        $(|  <$bitor_type_sid>  :: new($bitor_sid)  .raw )*
-                        $(+  <$add_type_sid>    :: new($add_sid)    .raw )*
-                        $(&  <$bitand_type_sid> :: new($bitand_sid) .raw )*
-                        $(^  <$bitxor_type_sid> :: new($bitxor_sid) .raw )*
-                        $(/  <$div_type_sid>    :: new($div_sid)    .raw )*
-                        $(*  <$mul_type_sid>    :: new($mul_sid)    .raw )*
-                        $(<< <$shl_type_sid>    :: new($shl_sid)    .raw )*
-                        $(>> <$shr_type_sid>    :: new($shr_sid)    .raw )*
-                    };
-                )+
+        impl ::core::convert::From<$name> for $known {
+            fn from(value: $name) -> Self {
+                value.to_known()
             }
-        )+
+        }
+
+        impl ::core::convert::From<$known> for $name {
+            fn from(known: $known) -> Self {
+                Self::from_known(known)
+            }
+        }
     };
-    (
-        $(#[$outermost_comment:meta])*
-        $whole_vis:vis mod $namespace:ident {
-            $(
-                $(#[$outer_comment:meta])*
-                $outer_vis:vis enum $name:ident {
-                    $($val_name:ident,$(#[$comment:meta])*)+
+}
+
+// Provides `Debug`, `Display` and `FromStr` using the variant names.
+// Flags are decomposed into their variants while enumerations only print exact matches.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __encap_enum_fmt {
+
+    (flags, $name:ident, $type:ty) => {
+        impl $name {
+            // Calls `each` with the name of every variant in `self`, then with `None` and the leftover bits if there are any.
+            fn __decompose<E>(&self, mut each: impl FnMut(::core::option::Option<&'static str>, $type) -> ::core::result::Result<(), E>) -> ::core::result::Result<(), E> {
+                if let ::core::option::Option::Some(name) = self.name() {
+                    return each(::core::option::Option::Some(name), self.raw);
                 }
-            )+
+                let mut remaining = self.raw;
+                for (name, value) in Self::iter_named() {
+                    if value.raw != 0 && self.contains(value) && remaining & value.raw != 0 {
+                        each(::core::option::Option::Some(name), value.raw)?;
+                        remaining &= !value.raw;
+                    }
+                }
+                if remaining != 0 || self.raw == 0 {
+                    each(::core::option::Option::None, remaining)?;
+                }
+                ::core::result::Result::Ok(())
+            }
         }
+        $crate::__encap_enum_fmt!{@common $name, $type}
+    };
+    (enumeration, $name:ident, $type:ty) => {
+        impl $name {
+            // Calls `each` with the name of the variant equal to `self`, or with `None` if there is no such variant.
+            fn __decompose<E>(&self, mut each: impl FnMut(::core::option::Option<&'static str>, $type) -> ::core::result::Result<(), E>) -> ::core::result::Result<(), E> {
+                each(self.name(), self.raw)
+            }
+        }
+        $crate::__encap_enum_fmt!{@common $name, $type}
+    };
+    (@common $name:ident, $type:ty) => {
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut first = true;
+                self.__decompose(|name, raw| {
+                    if !first { f.write_str(" | ")?; }
+                    first = false;
+                    match name {
+                        ::core::option::Option::Some(name) => f.write_str(name),
+                        ::core::option::Option::None => write!(f, "{:#x}", raw),
+                    }
+                })
+            }
+        }
+        impl ::core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                if f.alternate() {
+                    return f.debug_struct(stringify!($name)).field("raw", &self.raw).finish();
+                }
+                f.write_str(concat!(stringify!($name), "("))?;
+                ::core::fmt::Display::fmt(self, f)?;
+                f.write_str(")")
+            }
+        }
+        impl ::core::str::FromStr for $name {
+            type Err = $crate::ParseError;
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                $crate::__private::parse(s, Self::__parse_token, |left, right| Self{raw: left.raw | right.raw})
+            }
+        }
+        impl $name {
+            // Reads a single variant name or number.
+            fn __parse_token(token: &str) -> ::core::option::Option<Self> {
+                match Self::from_name(token) {
+                    ::core::option::Option::Some(value) => ::core::option::Option::Some(value),
+                    ::core::option::Option::None => <$type as $crate::__private::Integer>::parse_token(token).map(|raw| Self{raw}),
+                }
+            }
+        }
+    };
+}
+
+// Declares the struct of an `encap_enum!` along with every generated trait and method.
+// Option attributes such as `#[serde(names)]` and `#[flags]` are taken out of the outer attributes before they are applied to the struct.
+// The options are kept in order as the serde representation, the mode, the derived traits, the operator groups, the checks and the name of the known enum.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __encap_enum_struct {
+
+    // An enum under a `mod` without a type is an `isize` with private fields.
+    (@typed $attrs:tt $outer_vis:tt $name:ident [] $($item:tt)+) => {
+        $crate::__encap_enum_struct!{$attrs $outer_vis $name [] isize $($item)+}
+    };
+    (@typed $attrs:tt $outer_vis:tt $name:ident [$inner_vis:vis $type:ty] $($item:tt)+) => {
+        $crate::__encap_enum_struct!{$attrs $outer_vis $name [$inner_vis] $type $($item)+}
+    };
+    ([$($attr:tt)*] $($item:tt)+) => {
+        $crate::__encap_enum_struct!{@attrs [$($attr)*] [] [] [] [PartialEq, Eq, PartialOrd, Ord, Hash] [bitwise arithmetic shift assign] [] [] $($item)+}
+    };
+    (@attrs [#[serde $serde:tt] $($rest:tt)*] [$($kept:tt)*] [$($old_serde:tt)*] $mode:tt $derives:tt $ops:tt $checks:tt $known:tt $($item:tt)+) => {
+        $crate::__encap_enum_struct!{@attrs [$($rest)*] [$($kept)*] [$serde] $mode $derives $ops $checks $known $($item)+}
+    };
+    (@attrs [#[flags] $($rest:tt)*] [$($kept:tt)*] $serde:tt $mode:tt $derives:tt $ops:tt $checks:tt $known:tt $($item:tt)+) => {
+        $crate::__encap_enum_struct!{@attrs [$($rest)*] [$($kept)*] $serde [flags] $derives $ops $checks $known $($item)+}
+    };
+    (@attrs [#[enumeration] $($rest:tt)*] $kept:tt $serde:tt $mode:tt $derives:tt $ops:tt $checks:tt $known:tt $outer_vis:tt $name:ident $inner_vis:tt $type:ty, $kind:ident, $variants:tt) => {
+        $crate::__encap_enum_struct!{@attrs [$($rest)*] $kept $serde $mode $derives $ops $checks $known $outer_vis $name $inner_vis $type, enumeration, $variants}
+    };
+    (@attrs [#[unique] $($rest:tt)*] [$($kept:tt)*] $serde:tt $mode:tt $derives:tt $ops:tt [$($checks:ident)*] $known:tt $($item:tt)+) => {
+        $crate::__encap_enum_struct!{@attrs [$($rest)*] [$($kept)*] $serde $mode $derives $ops [$($checks)* unique] $known $($item)+}
+    };
+    (@attrs [#[disjoint] $($rest:tt)*] [$($kept:tt)*] $serde:tt $mode:tt $derives:tt $ops:tt [$($checks:ident)*] $known:tt $($item:tt)+) => {
+        $crate::__encap_enum_struct!{@attrs [$($rest)*] [$($kept)*] $serde $mode $derives $ops [$($checks)* disjoint] $known $($item)+}
+    };
+    (@attrs [#[single_bit] $($rest:tt)*] [$($kept:tt)*] $serde:tt $mode:tt $derives:tt $ops:tt [$($checks:ident)*] $known:tt $($item:tt)+) => {
+        $crate::__encap_enum_struct!{@attrs [$($rest)*] [$($kept)*] $serde $mode $derives $ops [$($checks)* single_bit] $known $($item)+}
+    };
+    (@attrs [#[known($known_name:ident)] $($rest:tt)*] [$($kept:tt)*] $serde:tt $mode:tt $derives:tt $ops:tt $checks:tt $known:tt $($item:tt)+) => {
+        $crate::__encap_enum_struct!{@attrs [$($rest)*] [$($kept)*] $serde $mode $derives $ops $checks [$known_name] $($item)+}
+    };
+    (@attrs [#[derives($($derive:tt)*)] $($rest:tt)*] [$($kept:tt)*] $serde:tt $mode:tt $derives:tt $ops:tt $checks:tt $known:tt $($item:tt)+) => {
+        $crate::__encap_enum_struct!{@attrs [$($rest)*] [$($kept)*] $serde $mode [$($derive)*] $ops $checks $known $($item)+}
+    };
+    (@attrs [#[set] $($rest:tt)*] [$($kept:tt)*] $serde:tt $mode:tt $derives:tt $ops:tt $checks:tt $known:tt $($item:tt)+) => {
+        $crate::__encap_enum_struct!{@attrs [$($rest)*] [$($kept)*] $serde $mode $derives [set assign] $checks $known $($item)+}
+    };
+    (@attrs [#[ops($($group:ident),* $(,)?)] $($rest:tt)*] $kept:tt $serde:tt $mode:tt $derives:tt $ops:tt $checks:tt $known:tt $($item:tt)+) => {
+        $crate::__encap_enum_struct!{@ops [$($group)*] [_ _ _] [$($group)*] [$($rest)*] $kept $serde $mode $derives $checks $known $($item)+}
+    };
+    // Looks through the operator groups for `bitwise`, `set` and `arithmetic`, before going back to the attributes.
+    // `set` implements the same operators as `bitwise`, and `-` like `arithmetic`.
+    (@ops [bitwise $($other:ident)*] [$bitwise:tt set $arithmetic:tt] $($item:tt)+) => {
+        compile_error!("`#[ops(..)]` cannot have both `bitwise` and `set`, since both implement `|`, `&`, `^` and `!`");
+    };
+    (@ops [set $($other:ident)*] [bitwise $set:tt $arithmetic:tt] $($item:tt)+) => {
+        compile_error!("`#[ops(..)]` cannot have both `bitwise` and `set`, since both implement `|`, `&`, `^` and `!`");
+    };
+    (@ops [set $($other:ident)*] [$bitwise:tt $set:tt arithmetic] $($item:tt)+) => {
+        compile_error!("`#[ops(..)]` cannot have both `set` and `arithmetic`, since both implement `-`");
+    };
+    (@ops [arithmetic $($other:ident)*] [$bitwise:tt set $arithmetic:tt] $($item:tt)+) => {
+        compile_error!("`#[ops(..)]` cannot have both `set` and `arithmetic`, since both implement `-`");
+    };
+    (@ops [bitwise $($other:ident)*] [$bitwise:tt $set:tt $arithmetic:tt] $($item:tt)+) => {
+        $crate::__encap_enum_struct!{@ops [$($other)*] [bitwise $set $arithmetic] $($item)+}
+    };
+    (@ops [set $($other:ident)*] [$bitwise:tt $set:tt $arithmetic:tt] $($item:tt)+) => {
+        $crate::__encap_enum_struct!{@ops [$($other)*] [$bitwise set $arithmetic] $($item)+}
+    };
+    (@ops [arithmetic $($other:ident)*] [$bitwise:tt $set:tt $arithmetic:tt] $($item:tt)+) => {
+        $crate::__encap_enum_struct!{@ops [$($other)*] [$bitwise $set arithmetic] $($item)+}
+    };
+    (@ops [$group:ident $($other:ident)*] $found:tt $($item:tt)+) => {
+        $crate::__encap_enum_struct!{@ops [$($other)*] $found $($item)+}
+    };
+    (@ops [] $found:tt $groups:tt $attrs:tt $kept:tt $serde:tt $mode:tt $derives:tt $checks:tt $known:tt $($item:tt)+) => {
+        $crate::__encap_enum_struct!{@attrs $attrs $kept $serde $mode $derives $groups $checks $known $($item)+}
+    };
+    (@attrs [#[$($attr:tt)*] $($rest:tt)*] [$($kept:tt)*] $serde:tt $mode:tt $derives:tt $ops:tt $checks:tt $known:tt $($item:tt)+) => {
+        $crate::__encap_enum_struct!{@attrs [$($rest)*] [$($kept)* #[$($attr)*]] $serde $mode $derives $ops $checks $known $($item)+}
+    };
+    (@attrs [] $kept:tt $serde:tt [flags] $derives:tt $ops:tt $checks:tt $known:tt $outer_vis:tt $name:ident $inner_vis:tt $type:ty, enumeration, $variants:tt) => {
+        compile_error!("`#[flags]` is not supported on enumerations");
+    };
+    // Without `#[flags]`, an enum where no variant has a value counts up from `0` like a fieldless `enum`, so it is an enumeration.
+    (@attrs [] $kept:tt $serde:tt [] $derives:tt $ops:tt $checks:tt $known:tt $outer_vis:tt $name:ident $inner_vis:tt $type:ty, flags,
+        [$({[$($attr:tt)*] $val_name:ident []})+]
     ) => {
-        $(#[$outermost_comment])*
-        #[allow(non_camel_case_types)]
-        $whole_vis mod $namespace {
-            mod __encap_enum {
+        $crate::__encap_enum_struct!{@attrs [] $kept $serde [] $derives $ops $checks $known $outer_vis $name $inner_vis $type, enumeration, [$({[$($attr)*] $val_name []})+]}
+    };
+    (@attrs [] [$($kept:tt)*] [$($serde:tt)*] [$($mode:tt)*] [$($derive:tt)*] [$($group:ident)*] [$($check:ident)*] [$($known:ident)?] [$($outer_vis:tt)*] $name:ident [$($inner_vis:tt)*] $type:ty, $kind:ident,
+        [$({[$($attr:tt)*] $val_name:ident $value:tt})+]
+    ) => {
+        $($kept)*
+        #[derive(Copy, Clone, $($derive)*)]
+        $($outer_vis)* struct $name{
+            $($inner_vis)* raw: $type
+        }
+        impl $crate::__private::Raw<$type> for $name {}
+        impl $name {
+            $($inner_vis)* const fn new(data: $type) -> Self{
+                Self{raw: data}
+            }
+
+            /// The raw value in little-endian byte order, like `to_le` on the integer type.
+            $($inner_vis)* const fn to_le(self) -> $type {
+                self.raw.to_le()
+            }
+
+            $($inner_vis)* fn get_bit(&self, bit:u8) -> bool{
+
+                self.raw & (1 << bit) != 0
+            }
+        }
+        $crate::__encap_enum_variants!{$name, $type, [$($mode)*], [$({[$($attr)*] $val_name})+], [_ $($val_name)+] [$({[$($attr)*] $val_name $value})+ {}]}
+        $crate::__encap_enum_impl!{$name, $type, [$($group)*] [$($group)*]}
+        $crate::__encap_enum_methods!{$name, $type, $({[$($attr)*] $val_name})+}
+        $crate::__encap_enum_lookup!{$name, $type, [$({[$($attr)*] $val_name})+], [_ $($val_name)+] [$({[$($attr)*] $val_name})+ {}]}
+        $($crate::__encap_enum_check!{$check $name})*
+        $crate::__encap_enum_known!{[$($known)?] [$($outer_vis)*] $name, $type, $({[$($attr)*] $val_name})+}
+        $crate::__encap_enum_fmt!{$kind, $name, $type}
+        $crate::__encap_enum_serde!{$($serde)* $kind, $name, $type}
+    };
+}
+
+// Provides `Serialize` and `Deserialize`. Selected with `#[serde(numeric)]`, `#[serde(name)]` or `#[serde(names)]`,
+// optionally followed by `strict` to reject values that are not made of variants.
+#[cfg(feature = "serde")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __encap_enum_serde {
+
+    ($kind:ident, $name:ident, $type:ty) => { $crate::__encap_enum_serde!{@numeric retain $kind, $name, $type} };
+    ((strict) $kind:ident, $name:ident, $type:ty) => { $crate::__encap_enum_serde!{@numeric strict $kind, $name, $type} };
+    (($repr:ident) $kind:ident, $name:ident, $type:ty) => { $crate::__encap_enum_serde!{@$repr retain $kind, $name, $type} };
+    (($repr:ident, strict) $kind:ident, $name:ident, $type:ty) => { $crate::__encap_enum_serde!{@$repr strict $kind, $name, $type} };
+
+    (@numeric $strict:ident $kind:ident, $name:ident, $type:ty) => {
+        impl $crate::__private::serde::Serialize for $name {
+            fn serialize<__S: $crate::__private::serde::Serializer>(&self, serializer: __S) -> ::core::result::Result<__S::Ok, __S::Error> {
+                $crate::__private::serde::Serialize::serialize(&self.raw, serializer)
+            }
+        }
+        impl<'de> $crate::__private::serde::Deserialize<'de> for $name {
+            fn deserialize<__D: $crate::__private::serde::Deserializer<'de>>(deserializer: __D) -> ::core::result::Result<Self, __D::Error> {
+                let value = Self{raw: <$type as $crate::__private::serde::Deserialize>::deserialize(deserializer)?};
+                $crate::__encap_enum_serde!(@$strict $kind, value, __D)
+            }
+        }
+    };
+    (@name $strict:ident $kind:ident, $name:ident, $type:ty) => {
+        impl $crate::__private::serde::Serialize for $name {
+            fn serialize<__S: $crate::__private::serde::Serializer>(&self, serializer: __S) -> ::core::result::Result<__S::Ok, __S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+        impl<'de> $crate::__private::serde::Deserialize<'de> for $name {
+            fn deserialize<__D: $crate::__private::serde::Deserializer<'de>>(deserializer: __D) -> ::core::result::Result<Self, __D::Error> {
+                $crate::__encap_enum_serde!{@visitor $name}
+                let value = deserializer.deserialize_str(__Visitor)?;
+                $crate::__encap_enum_serde!(@$strict $kind, value, __D)
+            }
+        }
+    };
+    (@names $strict:ident $kind:ident, $name:ident, $type:ty) => {
+        impl $crate::__private::serde::Serialize for $name {
+            fn serialize<__S: $crate::__private::serde::Serializer>(&self, serializer: __S) -> ::core::result::Result<__S::Ok, __S::Error> {
+                use $crate::__private::serde::ser::SerializeSeq;
+
+                let mut len = 0;
+                let _ = self.__decompose(|_, _| -> ::core::result::Result<(), ()> { len += 1; ::core::result::Result::Ok(()) });
+                let mut seq = serializer.serialize_seq(::core::option::Option::Some(len))?;
+                self.__decompose(|name, raw| match name {
+                    ::core::option::Option::Some(name) => seq.serialize_element(name),
+                    ::core::option::Option::None => seq.serialize_element(&format_args!("{:#x}", raw)),
+                })?;
+                seq.end()
+            }
+        }
+        impl<'de> $crate::__private::serde::Deserialize<'de> for $name {
+            fn deserialize<__D: $crate::__private::serde::Deserializer<'de>>(deserializer: __D) -> ::core::result::Result<Self, __D::Error> {
+                $crate::__encap_enum_serde!{@visitor $name}
+                struct __Element($name);
+                impl<'de> $crate::__private::serde::Deserialize<'de> for __Element {
+                    fn deserialize<__D: $crate::__private::serde::Deserializer<'de>>(deserializer: __D) -> ::core::result::Result<Self, __D::Error> {
+                        deserializer.deserialize_str(__Visitor).map(__Element)
+                    }
+                }
+                struct __SeqVisitor;
+                impl<'de> $crate::__private::serde::de::Visitor<'de> for __SeqVisitor {
+                    type Value = $name;
+                    fn expecting(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        f.write_str(concat!("a list of ", stringify!($name), " variant names"))
+                    }
+                    fn visit_seq<__A: $crate::__private::serde::de::SeqAccess<'de>>(self, mut seq: __A) -> ::core::result::Result<$name, __A::Error> {
+                        let mut value = $name{raw: 0};
+                        while let ::core::option::Option::Some(__Element(element)) = seq.next_element()? {
+                            value.raw |= element.raw;
+                        }
+                        ::core::result::Result::Ok(value)
+                    }
+                }
+                let value = deserializer.deserialize_seq(__SeqVisitor)?;
+                $crate::__encap_enum_serde!(@$strict $kind, value, __D)
+            }
+        }
+    };
+    (@visitor $name:ident) => {
+        struct __Visitor;
+        impl<'de> $crate::__private::serde::de::Visitor<'de> for __Visitor {
+            type Value = $name;
+            fn expecting(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(concat!("a ", stringify!($name), " variant name or number"))
+            }
+            fn visit_str<__E: $crate::__private::serde::de::Error>(self, v: &str) -> ::core::result::Result<$name, __E> {
+                v.parse().map_err(|_| __E::invalid_value($crate::__private::serde::de::Unexpected::Str(v), &self))
+            }
+        }
+    };
+    (@retain $kind:ident, $value:ident, $d:ident) => { ::core::result::Result::Ok($value) };
+    (@strict flags, $value:ident, $d:ident) => {
+        if Self::from_bits($value.raw).is_some() {
+            ::core::result::Result::Ok($value)
+        } else {
+            ::core::result::Result::Err(<$d::Error as $crate::__private::serde::de::Error>::custom(format_args!("{:?} has unknown bits", $value)))
+        }
+    };
+    (@strict enumeration, $value:ident, $d:ident) => {
+        if Self::from_raw($value.raw).is_some() {
+            ::core::result::Result::Ok($value)
+        } else {
+            ::core::result::Result::Err(<$d::Error as $crate::__private::serde::de::Error>::custom(format_args!("{:?} is not a variant", $value)))
+        }
+    };
+
+    ($($other:tt)*) => {
+        compile_error!("expected `#[serde(numeric)]`, `#[serde(name)]` or `#[serde(names)]`, optionally followed by `strict`");
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __encap_enum_serde {
+    ($($ignored:tt)*) => {};
+}
+
+// Declares the variant constants. Every variant is paired with the one before it, so a variant without a value continues from the previous one.
+// Called with the names of the variants prefixed by `_` and the variants followed by `{}`, which makes both lists the same length.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __encap_enum_variants {
+
+    ($name:ident, $type:ty, $mode:tt, [$({[$($other_attr:tt)*] $other:ident})+], [$($prev:tt)+] [$($variant:tt)+]) => {
+        const _: () = {
+            // The value of the last variant up to each variant that is not removed by `#[cfg]`, which is where a variant without a value continues from.
+            #[allow(dead_code)]
+            struct __Last;
+            #[allow(deprecated, non_upper_case_globals, dead_code)]
+            impl __Last {
+                $($crate::__encap_enum_variants!{@last $name, $type, $prev $variant})+
+            }
+
+            #[allow(deprecated)]
+            impl $name {
+                $($crate::__encap_enum_variants!{@variant $name, $type, $mode, $prev $variant})+
+            }
+        };
+        const _: () = {
+            // The raw value of every variant, declared once so that any value can name the other variants directly.
+            $(
+                $($other_attr)*
+                #[allow(non_upper_case_globals, dead_code, deprecated)]
+                const $other: $type = $name :: $other .raw;
+            )+
+
+            #[allow(deprecated)]
+            impl $name {
+                $($crate::__encap_enum_variants!{@value $name, $type, $variant})+
+            }
+        };
+    };
+
+    (@last $name:ident, $type:ty, $prev:tt {}) => {};
+    (@last $name:ident, $type:ty, _ {[$($attr:tt)*] $val_name:ident $value:tt}) => {
+        const $val_name: ::core::option::Option<$type> = $crate::__encap_enum_cfg!{[$($attr)*] $type, $name :: $val_name .raw};
+    };
+    (@last $name:ident, $type:ty, $prev:ident {[$($attr:tt)*] $val_name:ident $value:tt}) => {
+        const $val_name: ::core::option::Option<$type> = $crate::__encap_enum_cfg!{[$($attr)*] $type, $name :: $val_name .raw, __Last :: $prev};
+    };
+
+    // A variant with a value, which is declared next to the raw value of every variant.
+    (@value $name:ident, $type:ty, {[$($attr:tt)*] $val_name:ident [$value:expr]}) => {
+        $($attr)*
+        #[allow(non_upper_case_globals)]
+        pub const $val_name: $name = $name { raw : <$type>::from_le($crate::__private::typed::<$type, _>($value).to_le()) };
+    };
+    (@value $name:ident, $type:ty, $variant:tt) => {};
+
+    // A variant without a value.
+    (@variant $name:ident, $type:ty, $mode:tt, $prev:tt {}) => {};
+    (@variant $name:ident, $type:ty, $mode:tt, $prev:tt {[$($attr:tt)*] $val_name:ident [$value:expr]}) => {};
+    (@variant $name:ident, $type:ty, [], _ {[$($attr:tt)*] $val_name:ident []}) => {
+        $($attr)*
+        #[allow(non_upper_case_globals)]
+        pub const $val_name: $name = $name { raw : 0 };
+    };
+    (@variant $name:ident, $type:ty, [], $prev:ident {[$($attr:tt)*] $val_name:ident []}) => {
+        $($attr)*
+        #[allow(non_upper_case_globals)]
+        pub const $val_name: $name = $name { raw : match __Last :: $prev {
+            ::core::option::Option::Some(last) => last + 1,
+            ::core::option::Option::None => 0,
+        }};
+    };
+    (@variant $name:ident, $type:ty, [flags], _ {[$($attr:tt)*] $val_name:ident []}) => {
+        $($attr)*
+        #[allow(non_upper_case_globals)]
+        pub const $val_name: $name = $name { raw : 1 };
+    };
+    // The bit after the highest bit of the previous variant, so implicit flags continue past composites.
+    (@variant $name:ident, $type:ty, [flags], $prev:ident {[$($attr:tt)*] $val_name:ident []}) => {
+        $($attr)*
+        #[allow(non_upper_case_globals)]
+        pub const $val_name: $name = $name { raw : match __Last :: $prev {
+            ::core::option::Option::Some(last) => {
+                let bits = (::core::mem::size_of::<$type>() * 8) as u32;
+                let next = bits - last.leading_zeros();
+                if next >= bits {
+                    panic!("a `#[flags]` variant does not fit in the type of the enum");
+                }
+                1 << next
+            }
+            ::core::option::Option::None => 1,
+        }};
+    };
+}
+
+/**
+Combines variants of an `encap_enum!` in a constant expression.
+
+Operators cannot be used in a `const` or `static` item, so `flags!` builds the value with `const fn` instead.
+Without any variants the value is `empty()`.
+
+## Example
+```rust
+#[macro_use]
+extern crate encap_enum;
+
+encap_enum!{
+    enum Perms: u32 {
+        Read = 1,
+        Write = 2,
+        Exec = 4,
+    }
+}
+
+const READ_WRITE: Perms = flags!(Perms: Read | Write);
+
+fn main() {
+    assert_eq!(READ_WRITE, Perms::Read | Perms::Write);
+}
+```
+*/
+#[macro_export]
+macro_rules! flags {
+    ($name:path : $($val_name:ident)|*) => {
+        <$name>::empty()$(.union(<$name>::$val_name))*
+    };
+}
+
+/**
+A macro for bit flags and enumerations.
+
+See [crate level docs](https://docs.rs/encap_enum) for full documentation.
+
+## Example
+```rust
+#[macro_use]
+extern crate encap_enum;
+
+encap_enum!{
+    enum Flags{
+        A = 0x00,
+        B = 0x01,
+        C = 0x02,
+    }
+}
+
+fn main() {
+    println!("A = {}", Flags::A.raw);
+}
+```
+*/
+#[macro_export]
+macro_rules! encap_enum {
+    (
+        $(
+            $(#[$($outer_comment:tt)*])*
+            $outer_vis:vis enum $name:ident : $inner_vis:vis $type:ty {
                 $(
-                    #[allow(non_snake_case)]
-                    pub mod $name {
-                        pub enum $namespace {
-                            $($val_name,)+            
-                        }
-                    }
-                )+
+                    $(#[$comment:meta])*
+                    $val_name:ident $(= $value:expr)?
+                ),+ $(,)?
             }
+        )+
+    ) => {
+        $(
+            $crate::__encap_enum_struct!{[$(#[$($outer_comment)*])*] [$outer_vis] $name [$inner_vis] $type, flags, [$({[$(#[$comment])*] $val_name [$($value)?]})+]}
+        )+
+    };
+    (
+        $(
+        $(#[$($outer_comment:tt)*])*
+        $outer_vis:vis enum $name:ident {
             $(
-                $(#[$outer_comment])*
-                #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Hash)]
-                $outer_vis struct $name{
-                    raw: isize            
-                }
-                __encap_enum_impl!{$name, isize}            
-                
-                impl $name {                    
-                    pub fn iter() -> core::slice::Iter<'static, isize> {
-                        const _ARRAY: &[isize] = &[$($name :: $val_name .raw,)+];
-                        _ARRAY.into_iter()
-                    }    
-
-
-                    $(
-                        $(#[$comment])*
-                        #[allow(non_upper_case_globals)]
-                        pub const $val_name: $name = $name { raw : __encap_enum :: $name:: $namespace :: $val_name as isize};
-                    )+
-                }
-            )+
+                $(#[$comment:meta])*
+                $val_name:ident $(= $value:expr)?
+            ),+ $(,)?
         }
-    };    
+    )+
+    ) => {
+        $(
+            $crate::__encap_enum_struct!{[$(#[$($outer_comment)*])*] [$outer_vis] $name [] isize, flags, [$({[$(#[$comment])*] $val_name [$($value)?]})+]}
+        )+
+    };
     (
         $(#[$outermost_comment:meta])*
         $whole_vis:vis mod $namespace:ident {
             $(
-                $(#[$outer_comment:meta])*
-                $outer_vis:vis enum $name:ident : $inner_vis:vis $type:ty{
-                    $($val_name:ident,$(#[$comment:meta])*)+
+                $(#[$($outer_comment:tt)*])*
+                $outer_vis:vis enum $name:ident $(: $inner_vis:vis $type:ty)? {
+                    $(
+                        $(#[$comment:meta])*
+                        $val_name:ident $(= $value:expr)?
+                    ),+ $(,)?
                 }
             )+
         }
@@ -556,62 +2247,15 @@ macro_rules! encap_enum {
         $(#[$outermost_comment])*
         #[allow(non_camel_case_types)]
         $whole_vis mod $namespace {
-            mod __encap_enum {
-                $(
-                    #[allow(non_snake_case)]
-                    pub mod $name {
-                        pub enum $namespace {
-                            $($val_name,)+            
-                        }
-                    }
-                )+
-            }
             $(
-                $(#[$outer_comment])*
-                #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Hash)]
-                $outer_vis struct $name{
-                    $inner_vis raw: $type            
-                }
-                __encap_enum_impl!{$name, $type}
-                
-                impl $name {
-
-                    $inner_vis const fn new(data: $type) -> Self{
-                        Self{raw: data}
-                    }
-
-                    pub fn iter() -> core::slice::Iter<'static, $type> {
-                        const _ARRAY: &[$type] = &[$($name :: $val_name .raw,)+];
-                        _ARRAY.into_iter()
-                    }
-                    
-                    $inner_vis fn get_bit(&self, bit:u8) -> bool{
-
-                        self.raw & (1 << bit) != 0
-                    }
-
-                    $(
-                        $(#[$comment])*
-                        #[allow(non_upper_case_globals)]
-                        pub const $val_name: $name = $name { raw : __encap_enum :: $name:: $namespace :: $val_name as $type};
-                    )+
-                }
+                $crate::__encap_enum_struct!{@typed [$(#[$($outer_comment)*])*] [$outer_vis] $name [$($inner_vis $type)?], enumeration, [$({[$(#[$comment])*] $val_name [$($value)?]})+]}
             )+
         }
     };
-    (        
-        $(
-            $(#[$outer_comment:meta])*
-            $outer_vis:vis enum $name:ident : $inner_vis:vis $type:ty{
-                $($val_name:ident,$(#[$comment:meta])*)+
-            }
-        )+
-    ) => { compile_error!("encap_enum requires fieldless enums to be under a 'mod'.")}
 }
 
 
 
-
 #[cfg(test)]
 mod tests {
     encap_enum!(
@@ -677,7 +2321,7 @@ mod tests {
             Bard = (-Food) * 3,
             Jard = -3,
             Read = Jard - Bard,
-            Yard = (enum SignedEnum) TEST_CONST - (enum SignedEnum) TEST_CONST_TWO,
+            Yard = TEST_CONST - TEST_CONST_TWO,
         }
     }
 
@@ -697,13 +2341,14 @@ mod tests {
 
     // Verify the relationship between enum values and raw values.
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn value_integrity() {        
         let var0 = flag::TestEnum::Array;
         let var1 = flag::TestEnum::Bar | flag::TestEnum::Jar;
         assert_eq!(-OtherEnum::Bar.raw, -1); // Replacing OtherEnum with SpecificEnum results in an unsigned error, which is expected
         assert_eq!(var0.raw, 0);
         assert_eq!(var1.raw, 3);
-        assert_eq!(SignedEnum::Bard, 6.into());
+        assert_eq!(SignedEnum::Bard, SignedEnum::from(6));
         assert_eq!(SignedEnum::Yard.raw, TEST_CONST - TEST_CONST_TWO);
         assert_eq!(SignedEnum::Bard.get_bit(1), true);
     }
@@ -712,7 +2357,8 @@ mod tests {
     #[test]
     fn into_from() {
         let var: flag::TestEnum = flag::TestEnum::from(54);
-        assert_eq!(var, 54.into());
+        let other: flag::TestEnum = 54.into();
+        assert_eq!(var, other);
     }
 
     // Verify the enum can iterate
@@ -754,6 +2400,81 @@ mod tests {
         }
     }
 
+    #[test]
+    fn named_iteration() {
+        assert_eq!(flag::TestEnum::COUNT, 4);
+        assert_eq!(flag::TestEnum::NAMES, ["Array", "Bar", "Jar", "Foo"]);
+        assert_eq!(SignedEnum::VARIANTS[1], SignedEnum::Bard);
+
+        let mut variants = SpecificEnum::variants();
+        assert_eq!(variants.len(), 3);
+        assert_eq!(variants.next(), Some(SpecificEnum::Foo));
+        assert_eq!(variants.next_back(), Some(SpecificEnum::Rust));
+        assert_eq!(variants.len(), 1);
+
+        assert_eq!(OtherEnum::names().next_back(), Some("Rust"));
+        let mut named = flag::TestEnum::iter_named();
+        assert_eq!(named.len(), 4);
+        assert_eq!(named.next(), Some(("Array", flag::TestEnum::Array)));
+        assert_eq!(named.next_back(), Some(("Foo", flag::TestEnum::Foo)));
+    }
+
+    #[test]
+    fn set_iteration() {
+        encap_enum!{
+            enum Set: u8 {
+                None = 0,
+                A = 1,
+                B = 2,
+                C = 4,
+                AB = A | B,
+            }
+        }
+        let value = Set::A | Set::B | Set::new(0x80);
+        let mut set = value.iter_set();
+        assert_eq!(set.next(), Some(Set::A));
+        assert_eq!(set.next_back(), Some(Set::AB));
+        assert_eq!(set.next(), Some(Set::B));
+        assert_eq!(set.next(), None);
+        assert!(value.iter_set_names().rev().eq(["AB", "B", "A"].iter().copied()));
+        assert_eq!(value.remainder(), Set::new(0x80));
+        assert_eq!(Set::C.remainder(), Set::None);
+        assert_eq!(Set::None.iter_set().count(), 0);
+
+        let mut count = 0;
+        for flag in Set::A | Set::C {
+            assert!(flag == Set::A || flag == Set::C);
+            count += 1;
+        }
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn lookup() {
+        const GREATER: Option<flag::TestEnum> = flag::TestEnum::from_raw(3);
+        assert_eq!(GREATER, Some(flag::TestEnum::Foo));
+        assert_eq!(flag::TestEnum::from_raw(4), None);
+        assert_eq!(SignedEnum::from_raw(-9), Some(SignedEnum::Read));
+
+        const NAME: Option<&str> = SpecificEnum::Bar.name();
+        assert_eq!(NAME, Some("Bar"));
+        assert_eq!(SpecificEnum::new(3).name(), None);
+
+        assert_eq!(OtherEnum::from_name("Rust"), Some(OtherEnum::Rust));
+        assert_eq!(OtherEnum::from_name("rust"), None);
+        assert_eq!(OtherEnum::from_name_ignore_case("rUST"), Some(OtherEnum::Rust));
+        assert_eq!(OtherEnum::from_name_ignore_case("Rus"), None);
+
+        encap_enum!{
+            enum Shared: u8 {
+                First = 1,
+                Second = 1,
+            }
+        }
+        assert_eq!(Shared::Second.name(), Some("First"));
+        assert_eq!(Shared::from_name("Second"), Some(Shared::First));
+    }
+
     #[test]
     fn enumeration() {
         let mut count = 0;
@@ -821,13 +2542,702 @@ mod tests {
         z ^= t;
         z %= t;
     }
+    #[test]
+    fn flag_set() {
+        let mut a = SpecificEnum::Bar;
+        assert!(a.contains(SpecificEnum::Bar));
+        assert!(!a.contains(SpecificEnum::Bar | SpecificEnum::Rust));
+        assert!(a.intersects(SpecificEnum::Bar | SpecificEnum::Rust));
+        assert!(SpecificEnum::Foo.is_empty());
+
+        a.insert(SpecificEnum::Rust);
+        assert!(a.is_all());
+        a.remove(SpecificEnum::Bar);
+        assert_eq!(a, SpecificEnum::Rust);
+        a.toggle(SpecificEnum::Bar | SpecificEnum::Rust);
+        assert_eq!(a, SpecificEnum::Bar);
+        a.set(SpecificEnum::Rust, true);
+        a.set(SpecificEnum::Bar, false);
+        assert_eq!(a, SpecificEnum::Rust);
+
+        const UNION: SpecificEnum = SpecificEnum::Bar.union(SpecificEnum::Rust);
+        assert_eq!(UNION.raw, 3);
+        assert_eq!(UNION.intersection(SpecificEnum::Rust), SpecificEnum::Rust);
+        assert_eq!(UNION.difference(SpecificEnum::Rust), SpecificEnum::Bar);
+        assert_eq!(UNION.symmetric_difference(SpecificEnum::Bar), SpecificEnum::Rust);
+        assert_eq!(SpecificEnum::Bar.complement(), SpecificEnum::Rust);
+        assert_eq!(SpecificEnum::new(8).complement(), UNION);
+        assert_eq!(flag::TestEnum::Bar.complement(), flag::TestEnum::Jar);
+    }
+
+    #[test]
+    fn from_bits() {
+        assert_eq!(SpecificEnum::empty().raw, 0);
+        assert_eq!(SpecificEnum::all().raw, 3);
+        assert_eq!(SignedEnum::all().raw, -2 | 6 | -3 | -9 | 7);
+        assert_eq!(SpecificEnum::from_bits(2), Some(SpecificEnum::Rust));
+        assert_eq!(SpecificEnum::from_bits(0), Some(SpecificEnum::Foo));
+        assert_eq!(SpecificEnum::from_bits(4), None);
+        assert_eq!(SpecificEnum::from_bits_truncate(7).raw, 3);
+        assert_eq!(SpecificEnum::from_bits_retain(7).raw, 7);
+
+        const ALL: flag::TestEnum = flag::TestEnum::all();
+        assert_eq!(ALL.raw, 3);
+        assert!(flag::TestEnum::from_bits(4).is_none());
+    }
+
+    #[test]
+    fn debug() {
+        extern crate std;
+        use std::format;
+
+        assert_eq!(format!("{:?}", SpecificEnum::Foo), "SpecificEnum(Foo)");
+        assert_eq!(format!("{:?}", SpecificEnum::Bar | SpecificEnum::Rust), "SpecificEnum(Bar | Rust)");
+        assert_eq!(format!("{:?}", SpecificEnum::new(0x43)), "SpecificEnum(Bar | Rust | 0x40)");
+        assert_eq!(format!("{:?}", SpecificEnum::new(0x40)), "SpecificEnum(0x40)");
+        assert_eq!(format!("{:?}", OtherEnum::Bar), "OtherEnum(Bar)");
+        assert_eq!(format!("{:?}", flag::TestEnum::Foo), "TestEnum(Foo)");
+        assert_eq!(format!("{:?}", flag::TestEnum::new(9)), "TestEnum(0x9)");
+        assert_eq!(format!("{:#?}", SpecificEnum::Rust), "SpecificEnum {\n    raw: 2,\n}");
+    }
+
+    #[test]
+    fn display_from_str() {
+        extern crate std;
+        use std::string::ToString;
+        use crate::ParseErrorKind;
+
+        assert_eq!(SpecificEnum::Foo.to_string(), "Foo");
+        assert_eq!(SpecificEnum::new(0x43).to_string(), "Bar | Rust | 0x40");
+        assert_eq!(flag::TestEnum::Jar.to_string(), "Jar");
+        assert_eq!(flag::TestEnum::new(-1).to_string(), "0xffffffffffffffff");
+
+        for value in &[SpecificEnum::Foo, SpecificEnum::Rust, SpecificEnum::new(0x43), SpecificEnum::new(0x40)] {
+            assert_eq!(value.to_string().parse::<SpecificEnum>(), Ok(*value));
+        }
+        for value in &[flag::TestEnum::Bar, flag::TestEnum::new(-1), flag::TestEnum::new(9)] {
+            assert_eq!(value.to_string().parse::<flag::TestEnum>(), Ok(*value));
+        }
+        assert_eq!(" Bar|Rust ".parse::<SpecificEnum>(), Ok(SpecificEnum::Bar | SpecificEnum::Rust));
+        assert_eq!("Bar | 0b100 | 8".parse::<SpecificEnum>(), Ok(SpecificEnum::new(13)));
+        assert_eq!("-9".parse::<SignedEnum>(), Ok(SignedEnum::Read));
+
+        let error = "Bar | | Rust".parse::<SpecificEnum>().unwrap_err();
+        assert_eq!(error.kind(), ParseErrorKind::Empty);
+        assert_eq!(error.span(), 6..6);
+        let error = "Bar | Jar".parse::<SpecificEnum>().unwrap_err();
+        assert_eq!(error.kind(), ParseErrorKind::UnknownName);
+        assert_eq!(error.token("Bar | Jar"), "Jar");
+        let error = "0x1_0000_0000".parse::<SpecificEnum>().unwrap_err();
+        assert_eq!(error.kind(), ParseErrorKind::InvalidNumber);
+        assert_eq!("0x".parse::<SpecificEnum>().unwrap_err().kind(), ParseErrorKind::InvalidNumber);
+        assert_eq!(error.to_string(), "invalid number `0x1_0000_0000` at bytes 0..13");
+        assert_eq!(error.text(), "0x1_0000_0000");
+        let long = "Bar | xÉéééééééééééééééé";
+        let error = long.parse::<SpecificEnum>().unwrap_err();
+        assert!(error.is_truncated());
+        assert_eq!(error.text(), &long[6..37]);
+        assert_eq!(error.to_string(), std::format!("unknown variant name `{}...` at bytes 6..41", &long[6..37]));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        encap_enum!{
+            pub mod named {
+                #[serde(name, strict)]
+                pub enum Strict: pub u8 {
+                    Alpha,
+                    Beta,
+                }
+            }
+        }
+        encap_enum!{
+            #[serde(names)]
+            enum Names: u8 {
+                A = 1,
+                B = 2,
+            }
+            #[serde(names, strict)]
+            enum StrictNames: u8 {
+                A = 1,
+                B = 2,
+            }
+            #[serde(numeric, strict)]
+            enum StrictNumeric: u8 {
+                A = 1,
+                B = 2,
+            }
+        }
+        // Single letter names are also the names of generic parameters in the generated code.
+        encap_enum!{
+            #[serde(name, strict)]
+            enum D: u8 { X = 1, }
+            #[serde(name)]
+            enum E: u8 { X = 1, }
+            #[serde(names, strict)]
+            enum A: u8 { X = 1, }
+            #[serde(numeric)]
+            enum S: u8 { X = 1, }
+            #[serde(names)]
+            enum Visitor: u8 { X = 1, }
+        }
+        use serde_json::{from_str, to_string};
+
+        assert_eq!(from_str::<D>(r#""X""#).unwrap(), D::X);
+        assert_eq!(to_string(&E::X).unwrap(), r#""X""#);
+        assert_eq!(from_str::<A>(r#"["X"]"#).unwrap(), A::X);
+        assert_eq!(to_string(&S::X).unwrap(), "1");
+        assert_eq!(from_str::<Visitor>(r#"["X"]"#).unwrap(), Visitor::X);
+
+        assert_eq!(to_string(&SpecificEnum::Rust).unwrap(), "2");
+        assert_eq!(from_str::<SpecificEnum>("7").unwrap().raw, 7);
+        assert_eq!(from_str::<StrictNumeric>("3").unwrap(), StrictNumeric::A | StrictNumeric::B);
+        assert!(from_str::<StrictNumeric>("4").is_err());
+
+        assert_eq!(to_string(&named::Strict::Beta).unwrap(), "\"Beta\"");
+        assert_eq!(from_str::<named::Strict>("\"Beta\"").unwrap(), named::Strict::Beta);
+        assert!(from_str::<named::Strict>("\"0x7\"").is_err());
+        assert!(from_str::<named::Strict>("\"Gamma\"").is_err());
+
+        assert_eq!(to_string(&(Names::A | Names::B | 8)).unwrap(), r#"["A","B","0x8"]"#);
+        assert_eq!(to_string(&Names::new(0)).unwrap(), r#"["0x0"]"#);
+        assert_eq!(from_str::<Names>(r#"["A", "0x8"]"#).unwrap().raw, 9);
+        assert_eq!(from_str::<Names>("[]").unwrap().raw, 0);
+        assert_eq!(from_str::<StrictNames>(r#"["B"]"#).unwrap(), StrictNames::B);
+        assert!(from_str::<StrictNames>(r#"["A", "0x8"]"#).is_err());
+        assert!(from_str::<StrictNames>(r#"["C"]"#).is_err());
+    }
+
+    #[test]
+    fn implicit_values() {
+        encap_enum!{
+            enum Implicit: u8 {
+                A,
+                B = 5,
+                C,
+                D = C | 8,
+                E,
+                F,
+            }
+            enum Fieldless: pub u32 {
+                A,
+                B,
+            }
+        }
+        encap_enum!{
+            enum Untyped {
+                A = -2,
+                B,
+                C,
+            }
+        }
+        assert_eq!(Implicit::VARIANTS.map(|v| v.raw), [0, 5, 6, 14, 15, 16]);
+        assert_eq!(Untyped::VARIANTS.map(|v| v.raw), [-2, -1, 0]);
+        assert_eq!(Fieldless::B.raw, 1);
+    }
+
+    #[test]
+    fn auto_flags() {
+        encap_enum!{
+            #[flags]
+            enum Perms: u8 {
+                Read,
+                Write,
+                Exec,
+                All = Read | Write | Exec,
+                Sticky,
+                High = 0x40,
+                Top,
+            }
+        }
+        encap_enum!{
+            #[flags]
+            #[derive(Default)]
+            enum Signed {
+                A,
+                B,
+            }
+        }
+        assert_eq!(Perms::VARIANTS.map(|v| v.raw), [1, 2, 4, 7, 8, 0x40, 0x80]);
+        assert_eq!(Perms::Read | Perms::Write | Perms::Exec, Perms::All);
+        assert_eq!(Signed::A.raw + Signed::B.raw, 3);
+        assert_eq!(Signed::default().raw, 0);
+    }
+
+    #[test]
+    fn const_expressions() {
+        const OUTER: u16 = 0x100;
+        const fn double(n: u16) -> u16 { n * 2 }
+        encap_enum!{
+            enum Expr: u16 {
+                A = 1,
+                B = (A | 2) << 2,
+                C = (A + 1) | B,
+                D = double(B) as u8 as u16,
+                E = OUTER,
+                F = [3, 5][1] - A,
+            }
+        }
+        assert_eq!(Expr::VARIANTS.map(|v| v.raw), [1, 12, 14, 24, 0x100, 4]);
+    }
+
+    #[test]
+    fn other_enums() {
+        encap_enum!{
+            enum Inner: i16 {
+                A = 3,
+                B = -4,
+            }
+        }
+        encap_enum!{
+            enum Outer: i16 {
+                A = Inner::B,
+                B = Inner::A.raw << 2,
+                C = A | B,
+                D = crate::tests::TEST_CONST as i16,
+            }
+        }
+        assert_eq!(Outer::VARIANTS.map(|v| v.raw), [-4, 12, -4, 64]);
+    }
+
+    #[test]
+    fn variant_attributes() {
+        extern crate std;
+        use std::format;
+        encap_enum!{
+            enum Documented: u8 {
+                /// The first variant.
+                #[deprecated]
+                First = 1,
+                /// Uses a deprecated variant.
+                Second = First << 1,
+            }
+        }
+        encap_enum!{
+            mod attributes {
+                pub enum Listed: pub u8 {
+                    /// The first variant.
+                    #[deprecated]
+                    First,
+                    Second
+                }
+            }
+        }
+        assert_eq!(Documented::Second.raw, 2);
+        assert_eq!(attributes::Listed::Second.raw, 1);
+        assert_eq!(format!("{:?}", Documented::Second), "Documented(Second)");
+    }
+
+    #[test]
+    fn cfg_variants() {
+        encap_enum!{
+            #[flags]
+            enum Gated: u8 {
+                A,
+                #[cfg(any())]
+                B = UNDEFINED,
+                C,
+                #[cfg(all())]
+                D,
+                #[cfg(any())]
+                E,
+            }
+        }
+        encap_enum!{
+            mod gated {
+                pub enum Listed: pub u8 {
+                    A,
+                    #[cfg(any())]
+                    B,
+                    C,
+                }
+            }
+        }
+        assert_eq!(Gated::COUNT, 3);
+        assert_eq!(Gated::VARIANTS.map(|v| v.raw), [1, 2, 4]);
+        assert_eq!(Gated::NAMES, ["A", "C", "D"]);
+        assert!(Gated::iter().copied().eq([1, 2, 4].iter().copied()));
+        assert_eq!(Gated::all().raw, 7);
+        assert_eq!(Gated::from_name("D"), Some(Gated::D));
+        assert_eq!(Gated::from_name("B"), None);
+        assert_eq!(Gated::new(2).name(), Some("C"));
+        assert_eq!(Gated::D.index(), Some(2));
+        assert_eq!(Gated::from_raw(4), Some(Gated::D));
+        assert_eq!(gated::Listed::VARIANTS.map(|v| v.raw), [0, 1]);
+        assert_eq!(gated::Listed::from_raw(1), Some(gated::Listed::C));
+    }
+
+    #[test]
+    fn options() {
+        encap_enum!{
+            #[flags]
+            #[ops(bitwise, assign)]
+            #[derives(PartialEq, Eq, Default)]
+            #[derive(PartialOrd)]
+            enum Bitwise: u8 {
+                A,
+                B,
+            }
+            #[ops(shift)]
+            #[derives()]
+            enum Shifted: u8 {
+                One = 1,
+            }
+        }
+        let mut flags = Bitwise::A | Bitwise::B;
+        flags &= !Bitwise::A;
+        assert_eq!(flags, Bitwise::B);
+        assert!(Bitwise::A < Bitwise::B);
+        assert_eq!(Bitwise::default().raw, 0);
+        assert_eq!((Shifted::One << Shifted::One).raw, 2);
+    }
+
+    #[test]
+    fn set_ops() {
+        encap_enum!{
+            #[set]
+            enum Set: u8 {
+                A = 0x01,
+                B = 0x02,
+                C = 0x04,
+            }
+        }
+        let mut set = Set::A | Set::B;
+        assert_eq!(set - Set::C, set);
+        assert_eq!(set - Set::B, Set::A);
+        assert_eq!(!set, Set::C);
+        assert_eq!(!Set::new(0x80), Set::A | Set::B | Set::C);
+        set -= Set::A;
+        assert_eq!(set, Set::B);
+        set ^= Set::C;
+        assert_eq!(set, Set::B | Set::C);
+    }
+
+    #[test]
+    fn ordering() {
+        encap_enum!{
+            enum Unordered: i8 {
+                High = 10,
+                Low = -3,
+                Mid = 4,
+            }
+        }
+        let mut values = [Unordered::Mid, Unordered::new(7), Unordered::High, Unordered::Low, Unordered::new(5)];
+        values.sort();
+        assert_eq!(values.map(|v| v.raw), [-3, 4, 5, 7, 10]);
+        values.sort_by(Unordered::cmp_declaration);
+        assert_eq!(values.map(|v| v.raw), [10, -3, 4, 5, 7]);
+        assert_eq!(Unordered::Low.index(), Some(1));
+        assert_eq!(Unordered::new(5).index(), None);
+        assert_eq!(Unordered::from_index(2), Some(Unordered::Mid));
+        assert_eq!(Unordered::from_index(3), None);
+        assert_eq!(Unordered::Low.max(Unordered::Mid), Unordered::Mid);
+    }
+
+    #[test]
+    fn overflow() {
+        encap_enum!{
+            enum Unsigned: u8 {
+                One = 1,
+                Max = 255,
+            }
+        }
+        encap_enum!{
+            enum Signed: i8 {
+                Min = -128,
+                One = 1,
+            }
+        }
+        assert_eq!((-Unsigned::One).raw, 255);
+        assert_eq!(-Signed::One, Signed::new(-1));
+        assert_eq!(-Signed::Min, Signed::Min);
+        assert_eq!(Unsigned::One.checked_sub(Unsigned::Max), None);
+        assert_eq!(Unsigned::Max.checked_div(Unsigned::new(0)), None);
+        assert_eq!(Unsigned::Max.checked_rem(Unsigned::new(7)), Some(Unsigned::new(3)));
+        assert_eq!(Unsigned::One.wrapping_sub(Unsigned::Max).raw, 2);
+        assert_eq!(Unsigned::Max.saturating_mul(Unsigned::Max), Unsigned::Max);
+        assert_eq!(Unsigned::One.saturating_sub(Unsigned::Max).raw, 0);
+        assert_eq!(Unsigned::One.checked_neg(), None);
+        assert_eq!(Signed::Min.checked_neg(), None);
+        assert_eq!(Signed::Min.wrapping_neg(), Signed::Min);
+        assert_eq!(Unsigned::One.checked_shl(8), None);
+        assert_eq!(Unsigned::One.checked_shl(7).map(|v| v.raw), Some(128));
+        assert_eq!(Unsigned::Max.wrapping_shr(9).raw, 127);
+        assert_eq!(Unsigned::Max.checked_shr(8), None);
+        assert_eq!(Unsigned::One.wrapping_shl(9).raw, 2);
+    }
+
+    #[test]
+    fn mixed_operators() {
+        let mut flags = SpecificEnum::Bar | 0x10;
+        assert_eq!(flags, 0x11);
+        assert!(flags > 0x10 && flags <= 0x11);
+        assert_eq!(flags & 0x01, SpecificEnum::Bar);
+        assert_eq!(SpecificEnum::Bar << 3, 8);
+        assert_eq!(SpecificEnum::Rust >> 1, SpecificEnum::Bar);
+        flags <<= 1;
+        flags >>= 2;
+        assert_eq!(flags, 0x08);
+        let (bar, rust) = (&SpecificEnum::Bar, &SpecificEnum::Rust);
+        assert_eq!(bar | rust, 3);
+        assert_eq!(*bar | rust, bar | *rust);
+        assert_eq!(rust - bar, SpecificEnum::Bar);
+        assert_eq!(!bar, !SpecificEnum::Bar);
+        assert_eq!(-&SignedEnum::Food, 2);
+    }
+
+    #[test]
+    fn const_helpers() {
+        encap_enum!{
+            #[set]
+            enum Set: u8 {
+                A = 0x01,
+                B = 0x02,
+            }
+        }
+        const BITS: SpecificEnum = SpecificEnum::Bar.or(SpecificEnum::Rust).xor(SpecificEnum::Bar);
+        const SHIFTED: SpecificEnum = SpecificEnum::Bar.shl(4).shr(1);
+        const NONE: Set = flags!(Set:);
+        const BOTH: Set = flags!(Set: A | B);
+        const ONLY_A: Set = BOTH.sub(Set::B);
+        assert_eq!(BITS, SpecificEnum::Rust);
+        assert_eq!(SHIFTED, 8);
+        assert_eq!(SpecificEnum::Bar.not(), !SpecificEnum::Bar);
+        assert_eq!(SpecificEnum::Rust.and(SpecificEnum::Bar), 0);
+        assert_eq!(NONE, Set::empty());
+        assert_eq!(ONLY_A, Set::A);
+        assert_eq!(ONLY_A.not(), Set::B);
+        assert_eq!(flags!(flag::TestEnum: Bar), flag::TestEnum::Bar);
+    }
+
+    #[test]
+    fn mod_values() {
+        encap_enum!{
+            mod status {
+                pub enum Code: pub(crate) u8 {
+                    Ready = 1,
+                    Waiting,
+                    Closed = Waiting + 8,
+                    Reset,
+                }
+                pub enum Level {
+                    Low = -1,
+                    High,
+                }
+            }
+        }
+        assert_eq!(status::Code::Waiting.raw, 2);
+        assert_eq!(status::Code::Reset.raw, 11);
+        assert_eq!(status::Code::new(10).name(), Some("Closed"));
+        assert!(status::Code::Reset.get_bit(3));
+        assert_eq!(status::Level::NAMES, ["Low", "High"]);
+        assert_eq!(status::Level::High.name(), Some("High"));
+    }
+
+    #[test]
+    fn enumeration_attribute() {
+        extern crate std;
+        use std::format;
+
+        encap_enum!{
+            #[enumeration]
+            enum Color: u8 {
+                Red,
+                Green,
+                Blue,
+            }
+        }
+        encap_enum!{
+            #[enumeration]
+            enum Light {
+                Red = 1,
+                Green,
+            }
+        }
+        encap_enum!{
+            enum Inferred: u8 {
+                Red,
+                Green,
+                Blue,
+            }
+        }
+        assert_eq!(Color::Blue.raw, 2);
+        assert_eq!(Light::Green.raw, 2);
+        assert_eq!(format!("{:?}", Inferred::new(3)), "Inferred(0x3)");
+        assert_eq!(format!("{}", Inferred::Green | Inferred::Blue), "0x3");
+        assert_eq!(format!("{}", Color::Green), "Green");
+        assert_eq!(format!("{:?}", Color::new(3)), "Color(0x3)");
+        assert_eq!(format!("{}", Light::new(3)), "0x3");
+        assert_eq!(Light::from_raw(1), Some(Light::Red));
+    }
+
+    #[test]
+    fn checks() {
+        encap_enum!{
+            #[unique]
+            #[disjoint]
+            #[single_bit]
+            enum Checked: i8 {
+                A = 0x01,
+                B = 0x02,
+                #[cfg(any())]
+                Removed = 0x01,
+                Sign = -128,
+            }
+        }
+        encap_enum!{
+            #[flags]
+            #[unique]
+            enum Aliased: u8 {
+                Read,
+                Write,
+                Both = Read | Write,
+            }
+        }
+        assert_eq!(Checked::COUNT, 3);
+        assert_eq!(Aliased::Both.raw, 3);
+    }
+
+    #[test]
+    fn known() {
+        encap_enum!{
+            #[enumeration]
+            #[known(KnownCode)]
+            enum Code: u8 {
+                Ok,
+                Error = 4,
+                #[cfg(any())]
+                Removed,
+                #[deprecated]
+                Failed = 4,
+                Retry,
+            }
+        }
+        assert_eq!(Code::Retry.to_known(), KnownCode::Retry);
+        assert_eq!(Code::new(4).to_known(), KnownCode::Error);
+        assert_eq!(Code::new(2).to_known(), KnownCode::Unknown(2));
+        #[allow(deprecated)]
+        let failed = KnownCode::Failed;
+        assert_eq!(Code::from_known(failed), Code::Error);
+        const RETRY: Code = Code::from_known(KnownCode::Retry);
+        assert_eq!(RETRY, Code::Retry);
+        assert_eq!(Code::from_known(KnownCode::Unknown(9)).raw, 9);
+        for code in Code::variants() {
+            assert_eq!(Code::from(KnownCode::from(code)), code);
+        }
+        let total: u8 = Code::iter().map(|&raw| match Code::new(raw).to_known() {
+            KnownCode::Ok => 1,
+            KnownCode::Error => 2,
+            #[allow(deprecated)]
+            KnownCode::Failed => 0,
+            KnownCode::Retry => 3,
+            KnownCode::Unknown(_) => 0,
+        }).sum();
+        assert_eq!(total, 1 + 2 + 2 + 3);
+    }
+
+    // The generated code uses full paths, so it also compiles where the prelude is shadowed.
+    #[allow(dead_code, clippy::upper_case_acronyms)]
+    mod shadowed {
+        type Result<T> = core::result::Result<T, ()>;
+        struct Option;
+        struct Some;
+        struct None;
+        struct Ok;
+        struct Err;
+
+        encap_enum!{
+            #[known(KnownFlags)]
+            #[serde(names, strict)]
+            #[unique]
+            pub enum Flags: pub u8 {
+                A = 1,
+                B,
+            }
+            #[flags]
+            #[set]
+            #[serde(name)]
+            pub enum Set: pub u16 {
+                A,
+                B,
+            }
+        }
+        encap_enum!{
+            pub mod listed {
+                pub enum Listed: pub i8 {
+                    A,
+                    B,
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shadowed_prelude() {
+        extern crate std;
+        use std::string::ToString;
+        use shadowed::{Flags, Set, KnownFlags, listed::Listed};
+
+        assert_eq!(Flags::from_raw(2), Some(Flags::B));
+        assert_eq!(Flags::B.to_known(), KnownFlags::B);
+        assert_eq!("A | 0x4".parse::<Flags>().map(|flags| flags.raw), Ok(5));
+        assert_eq!((Set::A | Set::B).to_string(), "A | B");
+        assert_eq!(Listed::B.name(), Some("B"));
+    }
+
+    // Every variant with a value can name every other variant, which has to stay linear in the number of variants.
+    #[test]
+    fn many_variants() {
+        encap_enum!{
+            enum Large: u16 {
+                V000 = 0, V001 = V000 + 1, V002 = V001 + 1, V003 = V002 + 1, V004 = V003 + 1, V005 = V004 + 1, V006 = V005 + 1, V007 = V006 + 1, V008 = V007 + 1, V009 = V008 + 1,
+                V010 = V009 + 1, V011 = V010 + 1, V012 = V011 + 1, V013 = V012 + 1, V014 = V013 + 1, V015 = V014 + 1, V016 = V015 + 1, V017 = V016 + 1, V018 = V017 + 1, V019 = V018 + 1,
+                V020 = V019 + 1, V021 = V020 + 1, V022 = V021 + 1, V023 = V022 + 1, V024 = V023 + 1, V025 = V024 + 1, V026 = V025 + 1, V027 = V026 + 1, V028 = V027 + 1, V029 = V028 + 1,
+                V030 = V029 + 1, V031 = V030 + 1, V032 = V031 + 1, V033 = V032 + 1, V034 = V033 + 1, V035 = V034 + 1, V036 = V035 + 1, V037 = V036 + 1, V038 = V037 + 1, V039 = V038 + 1,
+                V040 = V039 + 1, V041 = V040 + 1, V042 = V041 + 1, V043 = V042 + 1, V044 = V043 + 1, V045 = V044 + 1, V046 = V045 + 1, V047 = V046 + 1, V048 = V047 + 1, V049 = V048 + 1,
+                V050 = V049 + 1, V051 = V050 + 1, V052 = V051 + 1, V053 = V052 + 1, V054 = V053 + 1, V055 = V054 + 1, V056 = V055 + 1, V057 = V056 + 1, V058 = V057 + 1, V059 = V058 + 1,
+                V060 = V059 + 1, V061 = V060 + 1, V062 = V061 + 1, V063 = V062 + 1, V064 = V063 + 1, V065 = V064 + 1, V066 = V065 + 1, V067 = V066 + 1, V068 = V067 + 1, V069 = V068 + 1,
+                V070 = V069 + 1, V071 = V070 + 1, V072 = V071 + 1, V073 = V072 + 1, V074 = V073 + 1, V075 = V074 + 1, V076 = V075 + 1, V077 = V076 + 1, V078 = V077 + 1, V079 = V078 + 1,
+                V080 = V079 + 1, V081 = V080 + 1, V082 = V081 + 1, V083 = V082 + 1, V084 = V083 + 1, V085 = V084 + 1, V086 = V085 + 1, V087 = V086 + 1, V088 = V087 + 1, V089 = V088 + 1,
+                V090 = V089 + 1, V091 = V090 + 1, V092 = V091 + 1, V093 = V092 + 1, V094 = V093 + 1, V095 = V094 + 1, V096 = V095 + 1, V097 = V096 + 1, V098 = V097 + 1, V099 = V098 + 1,
+                V100 = V099 + 1, V101 = V100 + 1, V102 = V101 + 1, V103 = V102 + 1, V104 = V103 + 1, V105 = V104 + 1, V106 = V105 + 1, V107 = V106 + 1, V108 = V107 + 1, V109 = V108 + 1,
+                V110 = V109 + 1, V111 = V110 + 1, V112 = V111 + 1, V113 = V112 + 1, V114 = V113 + 1, V115 = V114 + 1, V116 = V115 + 1, V117 = V116 + 1, V118 = V117 + 1, V119 = V118 + 1,
+                V120 = V119 + 1, V121 = V120 + 1, V122 = V121 + 1, V123 = V122 + 1, V124 = V123 + 1, V125 = V124 + 1, V126 = V125 + 1, V127 = V126 + 1, V128 = V127 + 1, V129 = V128 + 1,
+                V130 = V129 + 1, V131 = V130 + 1, V132 = V131 + 1, V133 = V132 + 1, V134 = V133 + 1, V135 = V134 + 1, V136 = V135 + 1, V137 = V136 + 1, V138 = V137 + 1, V139 = V138 + 1,
+                V140 = V139 + 1, V141 = V140 + 1, V142 = V141 + 1, V143 = V142 + 1, V144 = V143 + 1, V145 = V144 + 1, V146 = V145 + 1, V147 = V146 + 1, V148 = V147 + 1, V149 = V148 + 1,
+                V150 = V149 + 1, V151 = V150 + 1, V152 = V151 + 1, V153 = V152 + 1, V154 = V153 + 1, V155 = V154 + 1, V156 = V155 + 1, V157 = V156 + 1, V158 = V157 + 1, V159 = V158 + 1,
+                V160 = V159 + 1, V161 = V160 + 1, V162 = V161 + 1, V163 = V162 + 1, V164 = V163 + 1, V165 = V164 + 1, V166 = V165 + 1, V167 = V166 + 1, V168 = V167 + 1, V169 = V168 + 1,
+                V170 = V169 + 1, V171 = V170 + 1, V172 = V171 + 1, V173 = V172 + 1, V174 = V173 + 1, V175 = V174 + 1, V176 = V175 + 1, V177 = V176 + 1, V178 = V177 + 1, V179 = V178 + 1,
+                V180 = V179 + 1, V181 = V180 + 1, V182 = V181 + 1, V183 = V182 + 1, V184 = V183 + 1, V185 = V184 + 1, V186 = V185 + 1, V187 = V186 + 1, V188 = V187 + 1, V189 = V188 + 1,
+                V190 = V189 + 1, V191 = V190 + 1, V192 = V191 + 1, V193 = V192 + 1, V194 = V193 + 1, V195 = V194 + 1, V196 = V195 + 1, V197 = V196 + 1, V198 = V197 + 1, V199 = V198 + 1,
+                V200 = V199 + 1, V201 = V200 + 1, V202 = V201 + 1, V203 = V202 + 1, V204 = V203 + 1, V205 = V204 + 1, V206 = V205 + 1, V207 = V206 + 1, V208 = V207 + 1, V209 = V208 + 1,
+                V210 = V209 + 1, V211 = V210 + 1, V212 = V211 + 1, V213 = V212 + 1, V214 = V213 + 1, V215 = V214 + 1, V216 = V215 + 1, V217 = V216 + 1, V218 = V217 + 1, V219 = V218 + 1,
+                V220 = V219 + 1, V221 = V220 + 1, V222 = V221 + 1, V223 = V222 + 1, V224 = V223 + 1, V225 = V224 + 1, V226 = V225 + 1, V227 = V226 + 1, V228 = V227 + 1, V229 = V228 + 1,
+                V230 = V229 + 1, V231 = V230 + 1, V232 = V231 + 1, V233 = V232 + 1, V234 = V233 + 1, V235 = V234 + 1, V236 = V235 + 1, V237 = V236 + 1, V238 = V237 + 1, V239 = V238 + 1,
+                V240 = V239 + 1, V241 = V240 + 1, V242 = V241 + 1, V243 = V242 + 1, V244 = V243 + 1, V245 = V244 + 1, V246 = V245 + 1, V247 = V246 + 1, V248 = V247 + 1, V249 = V248 + 1,
+                V250 = V249 + 1, V251 = V250 + 1, V252 = V251 + 1, V253 = V252 + 1, V254 = V253 + 1, V255 = V254 + 1, V256 = V255 + 1, V257 = V256 + 1, V258 = V257 + 1, V259 = V258 + 1,
+                V260 = V259 + 1, V261 = V260 + 1, V262 = V261 + 1, V263 = V262 + 1, V264 = V263 + 1, V265 = V264 + 1, V266 = V265 + 1, V267 = V266 + 1, V268 = V267 + 1, V269 = V268 + 1,
+                V270 = V269 + 1, V271 = V270 + 1, V272 = V271 + 1, V273 = V272 + 1, V274 = V273 + 1, V275 = V274 + 1, V276 = V275 + 1, V277 = V276 + 1, V278 = V277 + 1, V279 = V278 + 1,
+                V280 = V279 + 1, V281 = V280 + 1, V282 = V281 + 1, V283 = V282 + 1, V284 = V283 + 1, V285 = V284 + 1, V286 = V285 + 1, V287 = V286 + 1, V288 = V287 + 1, V289 = V288 + 1,
+                V290 = V289 + 1, V291 = V290 + 1, V292 = V291 + 1, V293 = V292 + 1, V294 = V293 + 1, V295 = V294 + 1, V296 = V295 + 1, V297 = V296 + 1, V298 = V297 + 1, V299 = V298 + 1,
+                V300 = V299 + 1, V301 = V300 + 1, V302 = V301 + 1, V303 = V302 + 1, V304 = V303 + 1, V305 = V304 + 1, V306 = V305 + 1, V307 = V306 + 1, V308 = V307 + 1, V309 = V308 + 1,
+                V310 = V309 + 1, V311 = V310 + 1, V312 = V311 + 1, V313 = V312 + 1, V314 = V313 + 1, V315 = V314 + 1, V316 = V315 + 1, V317 = V316 + 1, V318 = V317 + 1, V319 = V318 + 1,
+            }
+        }
+        assert_eq!(Large::COUNT, 320);
+        assert_eq!(Large::V319.raw, 319);
+        assert_eq!(Large::from_raw(200), Some(Large::V200));
+        assert_eq!(Large::V300.index(), Some(300));
+    }
+
     #[test]
     fn externvar(){
         const AQUA: u32 = 34;
         const TERA: u32 = 64;
         encap_enum!{
             enum ExternEnum: u32{
-                Aqua = (enum ExternEnum)AQUA + (enum ExternEnum)TERA,
+                Aqua = AQUA + TERA,
             }  
         }        
         assert_eq!(ExternEnum::Aqua.raw, AQUA + TERA);